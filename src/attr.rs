@@ -0,0 +1,223 @@
+use caca::*;
+use ::{AnsiColor, CanvasView, Color, Style};
+
+/// A character cell attribute, packing the foreground and background colors
+/// and the style flags the way libcaca stores them.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Attr(u32);
+
+impl Attr {
+    pub fn new(fg: &AnsiColor, bg: &AnsiColor, style: Style) -> Attr {
+        // ANSI colors are flagged with 0x40 to tell them apart from ARGB
+        // colors, as done by caca_set_color_ansi().
+        let fg_bits = (fg.as_byte() as u32 | 0x40) << 4;
+        let bg_bits = (bg.as_byte() as u32 | 0x40) << 18;
+        Attr(bg_bits | fg_bits | style.bits())
+    }
+
+    /// An attribute holding only style flags. Setting it on a canvas changes
+    /// the style but keeps the current colors.
+    pub fn from_style(style: Style) -> Attr {
+        Attr(style.bits())
+    }
+
+    pub fn from_bits(bits: u32) -> Attr {
+        Attr(bits)
+    }
+
+    pub fn bits(&self) -> u32 {
+        self.0
+    }
+
+    pub fn style(&self) -> Style {
+        Style::from_bits_truncate(self.0 & 0x0f)
+    }
+
+    pub fn with_style(&self, style: Style) -> Attr {
+        Attr((self.0 & !0x0f) | style.bits())
+    }
+
+    /// The attribute as a DOS color byte, with the background in the high
+    /// nibble and the foreground in the low nibble. ARGB colors are mapped
+    /// to the nearest ANSI colors.
+    pub fn to_ansi(&self) -> u8 {
+        unsafe { caca_attr_to_ansi(self.0) }
+    }
+
+    /// The foreground as the nearest ANSI color, or `None` if the attribute
+    /// holds no valid foreground, as with attributes made of style flags.
+    pub fn ansi_fg(&self) -> Option<AnsiColor> {
+        ansi_color(unsafe { caca_attr_to_ansi_fg(self.0) })
+    }
+
+    /// The background as the nearest ANSI color, or `None` if the attribute
+    /// holds no valid background.
+    pub fn ansi_bg(&self) -> Option<AnsiColor> {
+        ansi_color(unsafe { caca_attr_to_ansi_bg(self.0) })
+    }
+
+    /// The foreground as a 12-bit `0xRGB` value.
+    pub fn rgb12_fg(&self) -> u16 {
+        unsafe { caca_attr_to_rgb12_fg(self.0) }
+    }
+
+    /// The background as a 12-bit `0xRGB` value.
+    pub fn rgb12_bg(&self) -> u16 {
+        unsafe { caca_attr_to_rgb12_bg(self.0) }
+    }
+
+    /// The background and foreground colors as eight 4-bit channel values,
+    /// in background A, R, G, B then foreground A, R, G, B order.
+    pub fn to_argb64(&self) -> [u8; 8] {
+        let mut argb = [0; 8];
+        unsafe { caca_attr_to_argb64(self.0, &mut argb) };
+        argb
+    }
+}
+
+// libcaca passes color fields it does not recognize through unchanged, so only
+// the values AnsiColor::from_byte() knows are converted.
+fn ansi_color(byte: u8) -> Option<AnsiColor> {
+    match byte as u32 {
+        0x00..=0x0f | CACA_DEFAULT | CACA_TRANSPARENT => Some(AnsiColor::from_byte(byte)),
+        _                                            => None,
+    }
+}
+
+/// A 16-bit color with 4 bits per channel, packed as `0xARGB`. An alpha of
+/// 15 is fully opaque.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Argb4444(pub u16);
+
+impl Argb4444 {
+    /// Builds a color from channel values between 0 and 15.
+    pub fn new(a: u8, r: u8, g: u8, b: u8) -> Argb4444 {
+        Argb4444(((a & 0x0f) as u16) << 12 | ((r & 0x0f) as u16) << 8 |
+                 ((g & 0x0f) as u16) << 4 | (b & 0x0f) as u16)
+    }
+
+    /// Builds an opaque color from 8-bit channel values, dropping the low
+    /// bits of each channel.
+    pub fn from_rgb(r: u8, g: u8, b: u8) -> Argb4444 {
+        Argb4444::new(0x0f, r >> 4, g >> 4, b >> 4)
+    }
+}
+
+impl<'b> From<&'b Color> for Argb4444 {
+    /// `Color` channels are 12-bit values, as in dither palettes.
+    fn from(color: &'b Color) -> Argb4444 {
+        Argb4444::new((color.a >> 8) as u8, (color.r >> 8) as u8,
+                      (color.g >> 8) as u8, (color.b >> 8) as u8)
+    }
+}
+
+impl CanvasView {
+    /// Sets the colors used for drawing. Drivers and exporters limited to the
+    /// 16 ANSI colors use the closest matching ones.
+    pub fn set_color_argb(&mut self, fg: Argb4444, bg: Argb4444) {
+        unsafe { caca_set_color_argb(self.raw(), fg.0, bg.0) };
+    }
+
+    /// The attribute used for drawing.
+    pub fn attr(&self) -> Attr {
+        Attr(unsafe { caca_get_attr(self.raw(), -1, -1) })
+    }
+
+    /// The attribute of the cell at the given coordinates. Coordinates
+    /// outside the canvas give the attribute used for drawing.
+    pub fn get_attr(&self, x: i32, y: i32) -> Attr {
+        Attr(unsafe { caca_get_attr(self.raw(), x, y) })
+    }
+
+    /// Sets the attribute used for drawing. Attributes holding only style
+    /// flags keep the current colors.
+    pub fn set_attr(&mut self, attr: Attr) {
+        unsafe { caca_set_attr(self.raw(), attr.bits()) };
+    }
+
+    /// Clears the given style flags from the attribute used for drawing.
+    pub fn unset_attr(&mut self, style: Style) {
+        unsafe { caca_unset_attr(self.raw(), style.bits()) };
+    }
+
+    /// Toggles the given style flags in the attribute used for drawing.
+    pub fn toggle_attr(&mut self, style: Style) {
+        unsafe { caca_toggle_attr(self.raw(), style.bits()) };
+    }
+
+    /// Changes the attribute of a single cell, leaving its character as is.
+    pub fn put_attr(&mut self, x: i32, y: i32, attr: Attr) {
+        unsafe { caca_put_attr(self.raw(), x, y, attr.bits()) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::{Canvas, CACA_BOLD, CACA_UNDERLINE};
+    use super::*;
+
+    #[test]
+    fn test_attr() {
+        let mut canvas = Canvas::new(10, 10).unwrap();
+        let attr = Attr::new(&AnsiColor::Red, &AnsiColor::Blue, CACA_BOLD);
+        canvas.set_attr(attr);
+        assert_eq!(canvas.attr(), attr);
+
+        canvas.put_char(0, 0, 'x');
+        assert_eq!(canvas.get_attr(0, 0), attr);
+        assert_eq!(canvas.get_attr(0, 0).style(), CACA_BOLD);
+
+        canvas.toggle_attr(CACA_UNDERLINE);
+        canvas.unset_attr(CACA_BOLD);
+        assert_eq!(canvas.attr(), attr.with_style(CACA_UNDERLINE));
+
+        canvas.set_attr(Attr::from_style(CACA_BOLD));
+        assert_eq!(canvas.attr(), attr);
+
+        let plain = Attr::new(&AnsiColor::White, &AnsiColor::Black, Style::empty());
+        canvas.put_attr(0, 0, plain);
+        assert_eq!(canvas.get_attr(0, 0), plain);
+    }
+
+    #[test]
+    fn test_argb() {
+        assert_eq!(Argb4444::new(0xf, 0x1, 0x2, 0x3), Argb4444(0xf123));
+        assert_eq!(Argb4444::from_rgb(0xff, 0x80, 0x00), Argb4444(0xff80));
+        let color = Color { r: 0xfff, g: 0x000, b: 0x800, a: 0xfff };
+        assert_eq!(Argb4444::from(&color), Argb4444(0xff08));
+
+        let mut canvas = Canvas::new(1, 1).unwrap();
+        canvas.set_color_argb(Argb4444(0xf0f0), Argb4444(0xf000));
+        canvas.put_char(0, 0, 'x');
+        let html = String::from_utf8(canvas.export(::ExportFormat::Html).unwrap()).unwrap();
+        assert!(html.contains("#0f0"));
+    }
+
+    #[test]
+    fn test_color_conversion() {
+        let attr = Attr::new(&AnsiColor::LightRed, &AnsiColor::Blue, CACA_BOLD);
+        assert_eq!(attr.ansi_fg(), Some(AnsiColor::LightRed));
+        assert_eq!(attr.ansi_bg(), Some(AnsiColor::Blue));
+        assert_eq!(attr.to_ansi(), 0x1c);
+
+        let default = Attr::new(&AnsiColor::Default, &AnsiColor::Transparent, Style::empty());
+        assert_eq!(default.ansi_fg(), Some(AnsiColor::Default));
+        assert_eq!(default.ansi_bg(), Some(AnsiColor::Transparent));
+
+        // Attributes holding only style flags have no colors to convert.
+        let style_only = Attr::from_style(CACA_BOLD);
+        assert_eq!(style_only.ansi_fg(), None);
+        assert_eq!(style_only.ansi_bg(), None);
+        assert_eq!(Attr::from_bits(0x10).ansi_fg(), None);
+
+        let mut canvas = Canvas::new(1, 1).unwrap();
+        canvas.set_color_argb(Argb4444(0xff00), Argb4444(0xf080));
+        let attr = canvas.attr();
+        assert_eq!(attr.rgb12_fg(), 0xf00);
+        assert_eq!(attr.rgb12_bg(), 0x080);
+        // Blue is stored with one bit less of precision.
+        let argb = attr.to_argb64();
+        assert_eq!(&argb[1..3], &[0x0, 0x8]);
+        assert_eq!(&argb[5..7], &[0xf, 0x0]);
+    }
+}
@@ -2,14 +2,28 @@
 extern crate errno;
 extern crate libc;
 extern crate caca_sys as caca;
+#[cfg(feature = "png")] extern crate png;
 
+pub mod attr;
+pub mod charset;
+pub mod codec;
+pub mod dirty;
 pub mod dither;
 pub mod event;
+pub mod figfont;
+pub mod file;
+pub mod font;
 pub mod keyboard;
 pub mod primitives;
+pub mod transform;
 
+pub use attr::{Attr, Argb4444};
 pub use keyboard::Key;
+pub use dirty::Rect;
 pub use dither::Dither;
+pub use figfont::{FigFont, FigFontSmush};
+pub use font::Font;
+pub use codec::{ExportFormat, ImportFormat, export_formats, import_formats};
 
 pub use event::{
     Event,
@@ -24,17 +38,23 @@ pub use event::{
     EVENT_ANY,
 };
 
+use std::char;
+use std::cmp;
 use std::default::Default;
 use std::ffi::{CStr, CString};
+use std::fmt;
+use std::io;
 use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
 use std::ptr::null_mut;
+use std::slice::{self, Chunks};
 use std::time::Duration;
 
 use caca::*;
 use errno::errno;
-use libc::c_int;
+use libc::{c_char, c_int};
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum AnsiColor {
     Black,
     Blue,
@@ -108,7 +128,7 @@ impl AnsiColor {
 
 bitflags! {
     #[repr(C)]
-    flags Style: u32 {
+    pub flags Style: u32 {
         const CACA_BOLD = caca::CACA_BOLD,
         const CACA_ITALICS = caca::CACA_ITALICS,
         const CACA_UNDERLINE = caca::CACA_UNDERLINE,
@@ -130,7 +150,7 @@ impl Visibility {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Eq, PartialEq, Debug, Clone)]
 pub enum Driver {
     Null,
     Raw,
@@ -142,13 +162,14 @@ pub enum Driver {
     VGA,
     Win32,
     X11,
-    Unknown,
+    /// A driver this crate does not know of, by its libcaca name.
+    Unknown(String),
 }
 
 impl Driver {
     fn from_cstr(cs: &CStr) -> Driver {
-        let string = cs.to_str().unwrap();
-        match string {
+        let string = cs.to_string_lossy();
+        match &*string {
             "null"    => Driver::Null,
             "raw"     => Driver::Raw,
             "cocoa"   => Driver::Cocoa,
@@ -159,12 +180,12 @@ impl Driver {
             "vga"     => Driver::VGA,
             "win32"   => Driver::Win32,
             "x11"     => Driver::X11,
-            _         => Driver::Unknown,
+            _         => Driver::Unknown(string.into_owned()),
         }
     }
     fn to_cstring(&self) -> CString {
         let driver_name = match *self {
-            Driver::Unknown |
+            Driver::Unknown(ref name) => name,
             Driver::Null    => "null",
             Driver::Raw     => "raw",
             Driver::Cocoa   => "cocoa",
@@ -180,19 +201,21 @@ impl Driver {
     }
 }
 
-pub struct Display {
+/// A window or terminal showing a canvas. A display either creates its own
+/// canvas or mutably borrows one for as long as it lives, since libcaca
+/// resizes the canvas along with the display.
+pub struct Display<'c> {
     display: *mut CacaDisplayRaw,
-    _phantom: PhantomData<*mut ()>,
+    _phantom: PhantomData<&'c mut Canvas>,
 }
 
-#[derive(Clone, Copy)]
-pub struct InitOptions<'b, 'a: 'b> {
-    pub canvas: Option<&'b Canvas<'a>>,
+pub struct InitOptions<'c> {
+    pub canvas: Option<&'c mut Canvas>,
     pub driver: Option<Driver>,
     pub buffer_stderr: bool,
 }
 
-impl<'b, 'a> Default for InitOptions<'b, 'a> {
+impl<'c> Default for InitOptions<'c> {
     fn default() -> Self {
         InitOptions {
             canvas: None,
@@ -218,9 +241,22 @@ pub enum CacaError {
     InvalidGamma,
     InvalidContrast,
     InvalidFrameIndex,
+    UnsupportedFormat,
+    FontNotFound,
+    InvalidFontData,
+    FigfontNotSet,
+    DirtyRectsNotDisabled,
+    InvalidRect,
+    Io(io::Error),
     Unknown(i32),
 }
 
+impl From<io::Error> for CacaError {
+    fn from(err: io::Error) -> CacaError {
+        CacaError::Io(err)
+    }
+}
+
 pub struct Color {
     pub r: u32,
     pub g: u32,
@@ -228,10 +264,20 @@ pub struct Color {
     pub a: u32,
 }
 
+/// The character stored in the cell right of a fullwidth character.
+pub const MAGIC_FULLWIDTH: u32 = 0x000ffffe;
+
+/// The contents of a single character cell.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Cell {
+    pub ch: char,
+    pub attr: Attr,
+}
+
 pub type CacaResult = Result<(), CacaError>;
 
-impl Display {
-    pub fn new(opts: InitOptions) -> Result<Self, CacaError> {
+impl<'c> Display<'c> {
+    pub fn new(opts: InitOptions<'c>) -> Result<Self, CacaError> {
         let canvas_ptr = match opts.canvas {
             Some(canvas_) => unsafe { canvas_.as_mut_ptr() },
             None          => null_mut(),
@@ -282,19 +328,21 @@ impl Display {
         }
     }
 
-    #[cfg(never)]
-    pub fn driver_list() -> Vec<Driver> {
-        let c_list = unsafe { caca_get_display_driver_list() };
-        c_list.map(|str| str.as_ptr())
-            .map(|ptr| CStr::from_ptr(ptr).to_str().unwrap())
+    /// The drivers libcaca was built with, along with their descriptions.
+    pub fn driver_list() -> Vec<(Driver, String)> {
+        described_list(unsafe { caca_get_display_driver_list() })
+            .into_iter()
+            .map(|(name, description)| (Driver::from_cstr(name),
+                                        description.to_string_lossy().into_owned()))
+            .collect()
     }
 
-    pub fn canvas(&self) -> Canvas {
-        let mut displays = Vec::new();
-        displays.push(self);
-        Canvas {
+    /// The canvas shown by the display, whether the display created it or
+    /// borrowed it. The display manages the size of the canvas, so resizing
+    /// it fails with `CanvasInUse`.
+    pub fn canvas<'d>(&'d mut self) -> CanvasRef<'d> {
+        CanvasRef {
             canvas: unsafe { caca_get_canvas(self.display) },
-            displays: displays,
             _phantom: PhantomData,
         }
     }
@@ -370,7 +418,7 @@ impl Display {
     }
 }
 
-impl Drop for Display {
+impl<'c> Drop for Display<'c> {
     fn drop(&mut self) {
         unsafe {
             caca_free_display(self.display);
@@ -378,13 +426,67 @@ impl Drop for Display {
     }
 }
 
-pub struct Canvas<'a> {
+/// A canvas owned by the program, freed when dropped. Drawing and the other
+/// canvas functions are provided by `CanvasView`, which it derefs to.
+pub struct Canvas {
     canvas: *mut CacaCanvasRaw,
-    displays: Vec<&'a Display>,
     _phantom: PhantomData<*mut ()>,
 }
 
-impl<'a> Canvas<'a> {
+/// The functions common to owned canvases and canvases borrowed from a
+/// display. A `CanvasView` is only ever reached through a reference pointing
+/// at the libcaca canvas itself and cannot be built, moved out or replaced,
+/// so a borrowed canvas cannot be taken away from the display using it.
+pub struct CanvasView {
+    _private: [u8; 0],
+    _phantom: PhantomData<*mut ()>,
+}
+
+/// A canvas borrowed from a display. Unlike `Canvas`, it does not free the
+/// canvas when dropped, as the display still uses it.
+///
+/// It only derefs to a `CanvasView`, so it cannot be swapped for a `Canvas`:
+///
+/// ```compile_fail
+/// use caca::{Canvas, Display, InitOptions};
+///
+/// let mut display = Display::new(InitOptions::default()).unwrap();
+/// let _ = std::mem::replace(&mut *display.canvas(), Canvas::new(1, 1).unwrap());
+/// ```
+pub struct CanvasRef<'d> {
+    canvas: *mut CacaCanvasRaw,
+    _phantom: PhantomData<&'d mut CanvasView>,
+}
+
+impl Deref for Canvas {
+    type Target = CanvasView;
+
+    fn deref(&self) -> &CanvasView {
+        unsafe { &*(self.canvas as *const CanvasView) }
+    }
+}
+
+impl DerefMut for Canvas {
+    fn deref_mut(&mut self) -> &mut CanvasView {
+        unsafe { &mut *(self.canvas as *mut CanvasView) }
+    }
+}
+
+impl<'d> Deref for CanvasRef<'d> {
+    type Target = CanvasView;
+
+    fn deref(&self) -> &CanvasView {
+        unsafe { &*(self.canvas as *const CanvasView) }
+    }
+}
+
+impl<'d> DerefMut for CanvasRef<'d> {
+    fn deref_mut(&mut self) -> &mut CanvasView {
+        unsafe { &mut *(self.canvas as *mut CanvasView) }
+    }
+}
+
+impl Canvas {
     pub fn new(width: i32, height: i32) -> Result<Self, CacaError> {
         // FIXME: kludge, this should be detected by libcaca
         if width < 0 || height < 0 {
@@ -404,7 +506,6 @@ impl<'a> Canvas<'a> {
         } else {
             Ok(Canvas {
                 canvas: canvas,
-                displays: Vec::new(),
                 _phantom: PhantomData,
             })
         }
@@ -412,41 +513,172 @@ impl<'a> Canvas<'a> {
         // FIXME: The errno is always EINVAL here except with width and height 0!
     }
 
+    /// The number of cells the string takes when printed. Combining marks
+    /// and invisible format characters take none.
+    pub fn str_width(s: &str) -> usize {
+        s.chars().map(charset::char_width).sum()
+    }
+}
+
+impl CanvasView {
+    // The view lives at the address of the libcaca canvas.
+    fn raw(&self) -> *mut CacaCanvasRaw {
+        self as *const CanvasView as *mut CacaCanvasRaw
+    }
+
     pub unsafe fn as_mut_ptr(&self) -> *mut CacaCanvasRaw {
-        self.canvas
+        self.raw()
     }
 
     pub fn set_color_ansi(&mut self, fg: &AnsiColor, bg: &AnsiColor) {
-        unsafe { caca_set_color_ansi(self.canvas, fg.as_byte(), bg.as_byte()) };
+        unsafe { caca_set_color_ansi(self.raw(), fg.as_byte(), bg.as_byte()) };
     }
 
+    /// Prints a character, returning the number of cells it takes. Fullwidth
+    /// characters that would be split by the edges of the canvas are left
+    /// out. Combining marks take no cell: they are composed with the
+    /// character left of `x` when Unicode has a precomposed form for the
+    /// pair, and dropped otherwise.
     pub fn put_char(&mut self, x: i32, y: i32, c: char) -> usize {
-        unsafe { caca_put_char(self.canvas, x, y, c as u32) as usize }
+        let width = charset::char_width(c);
+        if width == 0 {
+            self.compose_left(x, y, c);
+        } else if fits_whole(x, width, self.width()) {
+            unsafe { caca_put_char(self.raw(), x, y, c as u32) };
+        }
+        width
+    }
+
+    // Replaces the character left of x with its composition with the mark,
+    // keeping the attribute of its cell.
+    fn compose_left(&mut self, x: i32, y: i32, mark: char) {
+        let mut base_x = x - 1;
+        if base_x > 0 && unsafe { caca_get_char(self.raw(), base_x, y) } == MAGIC_FULLWIDTH {
+            base_x -= 1;
+        }
+        if base_x < 0 || base_x >= self.width() || y < 0 || y >= self.height() {
+            return;
+        }
+        if let Some(composed) = charset::compose(self.get_char(base_x, y), mark) {
+            unsafe {
+                let attr = caca_get_attr(self.raw(), base_x, y);
+                caca_put_char(self.raw(), base_x, y, composed as u32);
+                caca_put_attr(self.raw(), base_x, y, attr);
+            }
+        }
+    }
+
+    /// The character at the given coordinates, or a space if they are
+    /// outside the canvas.
+    pub fn get_char(&self, x: i32, y: i32) -> char {
+        let c = unsafe { caca_get_char(self.raw(), x, y) };
+        char::from_u32(c).unwrap_or(char::REPLACEMENT_CHARACTER)
+    }
+
+    pub fn cell(&self, x: i32, y: i32) -> Option<Cell> {
+        if x < 0 || y < 0 || x >= self.width() || y >= self.height() {
+            return None;
+        }
+        Some(Cell {
+            ch: self.get_char(x, y),
+            attr: self.get_attr(x, y),
+        })
+    }
+
+    /// The characters of the canvas as UTF-32 values, row after row. The
+    /// cell right of a fullwidth character holds `MAGIC_FULLWIDTH`.
+    pub fn chars(&self) -> &[u32] {
+        let len = self.width() as usize * self.height() as usize;
+        if len == 0 {
+            return &[];
+        }
+        unsafe { slice::from_raw_parts(caca_get_canvas_chars(self.raw()), len) }
+    }
+
+    /// The attributes of the canvas as raw values, row after row. Use
+    /// `Attr::from_bits` to inspect them.
+    pub fn attrs(&self) -> &[u32] {
+        let len = self.width() as usize * self.height() as usize;
+        if len == 0 {
+            return &[];
+        }
+        unsafe { slice::from_raw_parts(caca_get_canvas_attrs(self.raw()), len) }
+    }
+
+    pub fn char_rows<'b>(&'b self) -> Chunks<'b, u32> {
+        self.chars().chunks(cmp::max(self.width(), 1) as usize)
+    }
+
+    pub fn attr_rows<'b>(&'b self) -> Chunks<'b, u32> {
+        self.attrs().chunks(cmp::max(self.width(), 1) as usize)
     }
 
+    /// Prints a string, returning the number of cells it takes. Characters
+    /// are put one by one like caca_put_str() does, which saves converting
+    /// the string to a C string and allows interior NULs. Fullwidth
+    /// characters are never split by the edges of the canvas, and combining
+    /// marks are composed as with `put_char`.
     pub fn put_str(&mut self, x: i32, y: i32, s: &str) -> usize {
-        let cstring = CString::new(s).unwrap();
-        unsafe { caca_put_str(self.canvas, x, y, cstring.as_ptr()) as usize}
+        let mut len = 0;
+        for c in s.chars() {
+            len += self.put_char(x + len as i32, y, c);
+        }
+        len
+    }
+
+    /// Writes a row of cells, each with its own attribute, without touching
+    /// the attribute used for drawing. Returns the number of cells written.
+    /// Cells holding combining marks are composed with the cell before them,
+    /// which keeps its attribute.
+    pub fn put_cells(&mut self, x: i32, y: i32, cells: &[Cell]) -> usize {
+        let canvas_width = self.width();
+        let mut len = 0;
+        for cell in cells {
+            let width = charset::char_width(cell.ch);
+            if width == 0 {
+                self.compose_left(x + len as i32, y, cell.ch);
+            } else if fits_whole(x + len as i32, width, canvas_width) {
+                unsafe {
+                    caca_put_char(self.raw(), x + len as i32, y, cell.ch as u32);
+                    caca_put_attr(self.raw(), x + len as i32, y, cell.attr.bits());
+                }
+            }
+            len += width;
+        }
+        len
+    }
+
+    /// Moves the cursor used by `write!`.
+    pub fn gotoxy(&mut self, x: i32, y: i32) {
+        unsafe { caca_gotoxy(self.raw(), x, y) };
+    }
+
+    pub fn wherex(&self) -> i32 {
+        unsafe { caca_wherex(self.raw()) }
+    }
+
+    pub fn wherey(&self) -> i32 {
+        unsafe { caca_wherey(self.raw()) }
     }
 
     pub fn clear(&mut self) {
-        unsafe { caca_clear_canvas(self.canvas) };
+        unsafe { caca_clear_canvas(self.raw()) };
     }
 
     pub fn set_handle(&mut self, x: i32, y: i32) {
-        unsafe { caca_set_canvas_handle(self.canvas, x, y) };
+        unsafe { caca_set_canvas_handle(self.raw(), x, y) };
     }
 
     pub fn handle_x(&self) -> i32 {
-        unsafe { caca_get_canvas_handle_x(self.canvas) }
+        unsafe { caca_get_canvas_handle_x(self.raw()) }
     }
 
     pub fn handle_y(&self) -> i32 {
-        unsafe { caca_get_canvas_handle_y(self.canvas) }
+        unsafe { caca_get_canvas_handle_y(self.raw()) }
     }
 
-    pub fn blit(&mut self, x: i32, y: i32, source: &Canvas, mask: &Canvas) -> CacaResult {
-        let result = unsafe { caca_blit(self.canvas, x, y, source.canvas, mask.canvas) };
+    pub fn blit(&mut self, x: i32, y: i32, source: &CanvasView, mask: &CanvasView) -> CacaResult {
+        let result = unsafe { caca_blit(self.raw(), x, y, source.raw(), mask.raw()) };
         if result == 0 {
             Ok(())
         } else {
@@ -459,7 +691,7 @@ impl<'a> Canvas<'a> {
     }
 
     pub fn set_boundaries(&mut self, x: i32, y: i32, w: i32, h: i32) -> CacaResult {
-        let result = unsafe { caca_set_canvas_boundaries(self.canvas, x, y, w, h) };
+        let result = unsafe { caca_set_canvas_boundaries(self.raw(), x, y, w, h) };
         if result == 0 {
             Ok(())
         } else {
@@ -474,7 +706,7 @@ impl<'a> Canvas<'a> {
     }
 
     pub fn set_size(&mut self, width: i32, height: i32) -> CacaResult {
-        let result = unsafe { caca_set_canvas_size(self.canvas, width, height) };
+        let result = unsafe { caca_set_canvas_size(self.raw(), width, height) };
         // FIXME: The errno is always EINVAL here except with width and height 0!
         if result == 0 {
             Ok(())
@@ -490,19 +722,19 @@ impl<'a> Canvas<'a> {
     }
 
     pub fn width(&self) -> i32 {
-        unsafe { caca_get_canvas_width(self.canvas) as i32 }
+        unsafe { caca_get_canvas_width(self.raw()) as i32 }
     }
 
     pub fn height(&self) -> i32 {
-        unsafe { caca_get_canvas_height(self.canvas) as i32 }
+        unsafe { caca_get_canvas_height(self.raw()) as i32 }
     }
 
     pub fn frame_count(&self) -> i32 {
-        unsafe { caca_get_frame_count(self.canvas) }
+        unsafe { caca_get_frame_count(self.raw()) }
     }
 
     pub fn set_frame(&mut self, frame_index: i32) -> CacaResult {
-        let result = unsafe { caca_set_frame(self.canvas, frame_index) };
+        let result = unsafe { caca_set_frame(self.raw(), frame_index) };
         if result == 0 {
             Ok(())
         } else {
@@ -517,7 +749,7 @@ impl<'a> Canvas<'a> {
 
     pub fn get_frame_name(&self) -> &str {
         unsafe {
-            let raw_str = caca_get_frame_name(self.canvas);
+            let raw_str = caca_get_frame_name(self.raw());
             let frame_name = CStr::from_ptr(raw_str);
             frame_name.to_str().unwrap()
         }
@@ -525,7 +757,7 @@ impl<'a> Canvas<'a> {
 
     pub fn set_frame_name(&mut self, name: &str) -> CacaResult {
         let name_cstring = CString::new(name).unwrap();
-        let result = unsafe { caca_set_frame_name(self.canvas, name_cstring.as_ptr()) };
+        let result = unsafe { caca_set_frame_name(self.raw(), name_cstring.as_ptr()) };
         if result == 0 {
             Ok(())
         } else {
@@ -538,7 +770,7 @@ impl<'a> Canvas<'a> {
     }
 
     pub fn create_frame(&mut self, frame_index: i32) -> CacaResult {
-        let result = unsafe { caca_create_frame(self.canvas, frame_index) };
+        let result = unsafe { caca_create_frame(self.raw(), frame_index) };
         if result == 0 {
             Ok(())
         } else {
@@ -551,7 +783,7 @@ impl<'a> Canvas<'a> {
     }
 
     pub fn remove_frame(&mut self, frame_index: i32) -> CacaResult {
-        let result = unsafe { caca_free_frame(self.canvas, frame_index) };
+        let result = unsafe { caca_free_frame(self.raw(), frame_index) };
         if result == 0 {
             Ok(())
         } else {
@@ -562,9 +794,70 @@ impl<'a> Canvas<'a> {
             }
         }
     }
+
+    /// The names of all frames, in order. Reading them requires switching
+    /// frames, but the current frame and the dirty rectangles are left
+    /// unchanged.
+    pub fn frame_names(&mut self) -> Vec<String> {
+        // Switching frames marks the whole canvas dirty.
+        self.disable_dirty_rect();
+        let current = self.current_frame();
+        let names = (0..self.frame_count()).map(|i| unsafe {
+            caca_set_frame(self.raw(), i);
+            CStr::from_ptr(caca_get_frame_name(self.raw())).to_string_lossy().into_owned()
+        }).collect();
+        unsafe { caca_set_frame(self.raw(), current) };
+        let _ = self.enable_dirty_rect();
+        names
+    }
+
+    // libcaca has no getter for the current frame index, but every frame has
+    // its own character buffer, which becomes the canvas buffer once the frame
+    // is selected.
+    fn current_frame(&mut self) -> i32 {
+        let chars = unsafe { caca_get_canvas_chars(self.raw()) };
+        let current = (0..self.frame_count()).find(|&i| unsafe {
+            caca_set_frame(self.raw(), i);
+            caca_get_canvas_chars(self.raw()) == chars
+        }).unwrap_or(0);
+        unsafe { caca_set_frame(self.raw(), current) };
+        current
+    }
+}
+
+/// Writes at the cursor and moves it past the text, wrapping to the start of
+/// the next line at the right edge of the canvas and on newlines.
+impl fmt::Write for CanvasView {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let width = self.width();
+        let (mut x, mut y) = (self.wherex(), self.wherey());
+        for c in s.chars() {
+            match c {
+                '\n' => {
+                    x = 0;
+                    y += 1;
+                },
+                '\r' => x = 0,
+                _     => {
+                    // Wrap early rather than split a fullwidth character.
+                    if x + charset::char_width(c) as i32 > width {
+                        x = 0;
+                        y += 1;
+                    }
+                    x += self.put_char(x, y, c) as i32;
+                    if x >= width {
+                        x = 0;
+                        y += 1;
+                    }
+                },
+            }
+        }
+        self.gotoxy(x, y);
+        Ok(())
+    }
 }
 
-impl<'a> Drop for Canvas<'a> {
+impl Drop for Canvas {
     fn drop(&mut self) {
         unsafe {
             caca_free_canvas(self.canvas);
@@ -572,6 +865,31 @@ impl<'a> Drop for Canvas<'a> {
     }
 }
 
+// Whether a character taking the given number of cells can be put at x
+// without being split by the edges of the canvas. Characters taking a single
+// cell are clipped by libcaca itself.
+fn fits_whole(x: i32, width: usize, canvas_width: i32) -> bool {
+    match width {
+        0 => false,
+        1 => true,
+        _ => x >= 0 && x + width as i32 <= canvas_width,
+    }
+}
+
+// Walks a NULL terminated list of name and description pairs, as returned by
+// the various libcaca list functions.
+fn described_list(list: *const *const c_char) -> Vec<(&'static CStr, &'static CStr)> {
+    let mut pairs = Vec::new();
+    unsafe {
+        let mut entry = list;
+        while !(*entry).is_null() {
+            pairs.push((CStr::from_ptr(*entry), CStr::from_ptr(*entry.offset(1))));
+            entry = entry.offset(2);
+        }
+    }
+    pairs
+}
+
 #[cfg(test)]
 mod tests {
     //! NOTE: These tests have to be run in a single-threaded environment
@@ -579,14 +897,12 @@ mod tests {
     //! thread-safe.
     use super::*;
 
-    fn get_canvas_and_display() -> (Canvas<'static>, Display) {
-        let canvas = Canvas::new(100, 100);
-        assert!(canvas.is_ok(), "{:?}", canvas.err());
-        let canvas_ok = canvas.unwrap();
-        let display = Display::new(InitOptions{canvas: Some(&canvas_ok),
+    fn null_display<'c>(canvas: Option<&'c mut Canvas>) -> Display<'c> {
+        let display = Display::new(InitOptions{canvas: canvas,
+                                               driver: Some(Driver::Null),
                                                ..InitOptions::default()});
         assert!(display.is_ok(), "{:?}", display.err());
-        (canvas_ok, display.unwrap())
+        display.unwrap()
     }
 
     #[test]
@@ -609,4 +925,206 @@ mod tests {
         let result = canvas.set_size(-100, -100);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_display_canvas() {
+        let mut display = null_display(None);
+        {
+            let mut canvas = display.canvas();
+            canvas.put_str(0, 0, "hi");
+            let result = canvas.set_size(5, 5);
+            assert!(matches!(result, Err(CacaError::CanvasInUse)), "{:?}", result);
+        }
+        // Dropping the borrowed canvas must leave it alive.
+        assert_eq!(display.canvas().get_char(0, 0), 'h');
+        display.refresh();
+    }
+
+    #[test]
+    fn test_swap_views() {
+        use std::mem;
+
+        let mut first = Canvas::new(2, 1).unwrap();
+        let mut second = Canvas::new(2, 1).unwrap();
+        first.put_char(0, 0, 'a');
+        second.put_char(0, 0, 'b');
+        // Views hold nothing, so swapping them leaves the canvases in place.
+        mem::swap(&mut *first, &mut *second);
+        assert_eq!(first.get_char(0, 0), 'a');
+        assert_eq!(second.get_char(0, 0), 'b');
+
+        let mut display = null_display(None);
+        mem::swap(&mut *display.canvas(), &mut *first);
+        assert_eq!(first.get_char(0, 0), 'a');
+        display.canvas().put_char(0, 0, 'c');
+        assert_eq!(display.canvas().get_char(0, 0), 'c');
+    }
+
+    #[test]
+    fn test_blit_from_display() {
+        let mut display = null_display(None);
+        display.canvas().put_str(0, 0, "hi");
+        let (width, height) = (display.canvas().width(), display.canvas().height());
+
+        let mut mask = Canvas::new(width, height).unwrap();
+        mask.put_str(0, 0, "##");
+        let mut canvas = Canvas::new(width, height).unwrap();
+        let result = canvas.blit(0, 0, &display.canvas(), &mask);
+        assert!(result.is_ok(), "{:?}", result.err());
+        assert_eq!(canvas.get_char(0, 0), 'h');
+        assert_eq!(canvas.get_char(1, 0), 'i');
+    }
+
+    #[test]
+    fn test_attached_canvas() {
+        let mut canvas = Canvas::new(20, 10).unwrap();
+        {
+            let mut display = null_display(Some(&mut canvas));
+            display.canvas().put_str(0, 0, "hi");
+            let result = display.canvas().set_size(5, 5);
+            assert!(matches!(result, Err(CacaError::CanvasInUse)), "{:?}", result);
+            display.refresh();
+        }
+        // Once the display is gone the canvas is ours again.
+        assert_eq!(canvas.get_char(1, 0), 'i');
+        let result = canvas.set_size(5, 5);
+        assert!(result.is_ok(), "{:?}", result.err());
+    }
+
+    #[test]
+    fn test_driver_list() {
+        let drivers = Display::driver_list();
+        assert!(drivers.iter().any(|(driver, _)| *driver == Driver::Null));
+        assert!(drivers.iter().all(|(_, description)| !description.is_empty()));
+
+        let name = CString::new("teletype").unwrap();
+        let driver = Driver::from_cstr(&name);
+        assert_eq!(driver, Driver::Unknown(String::from("teletype")));
+        assert_eq!(driver.to_cstring(), name);
+    }
+
+    #[test]
+    fn test_read_back() {
+        let mut canvas = Canvas::new(10, 5).unwrap();
+        canvas.set_color_ansi(&AnsiColor::Yellow, &AnsiColor::Black);
+        canvas.put_str(2, 3, "hi");
+
+        assert_eq!(canvas.get_char(2, 3), 'h');
+        assert_eq!(canvas.get_char(3, 3), 'i');
+        assert_eq!(canvas.get_char(-1, 3), ' ');
+
+        let attr = Attr::new(&AnsiColor::Yellow, &AnsiColor::Black, Style::empty());
+        assert_eq!(canvas.cell(3, 3), Some(Cell { ch: 'i', attr: attr }));
+        assert_eq!(canvas.cell(10, 0), None);
+        assert_eq!(canvas.cell(0, -1), None);
+    }
+
+    #[test]
+    fn test_buffers() {
+        let mut canvas = Canvas::new(4, 3).unwrap();
+        canvas.set_color_ansi(&AnsiColor::Red, &AnsiColor::Black);
+        canvas.put_str(1, 2, "ok");
+
+        assert_eq!(canvas.chars().len(), 12);
+        assert_eq!(canvas.chars()[2 * 4 + 1], 'o' as u32);
+        assert_eq!(Attr::from_bits(canvas.attrs()[2 * 4 + 2]), canvas.get_attr(2, 2));
+
+        let rows: Vec<&[u32]> = canvas.char_rows().collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[2], &[' ' as u32, 'o' as u32, 'k' as u32, ' ' as u32]);
+        assert_eq!(canvas.attr_rows().count(), 3);
+
+        let empty = Canvas::new(0, 0).unwrap();
+        assert!(empty.chars().is_empty());
+        assert_eq!(empty.char_rows().count(), 0);
+    }
+
+    #[test]
+    fn test_put_cells() {
+        let mut canvas = Canvas::new(10, 2).unwrap();
+        let red = Attr::new(&AnsiColor::Red, &AnsiColor::Black, Style::empty());
+        let blue = Attr::new(&AnsiColor::Blue, &AnsiColor::Black, Style::empty());
+        let cells = [Cell { ch: '@', attr: red }, Cell { ch: '#', attr: blue }];
+
+        let before = canvas.attr();
+        assert_eq!(canvas.put_cells(3, 1, &cells), 2);
+        assert_eq!(canvas.cell(3, 1), Some(cells[0]));
+        assert_eq!(canvas.cell(4, 1), Some(cells[1]));
+        assert_eq!(canvas.attr(), before);
+
+        assert_eq!(canvas.put_str(0, 0, "a\0b"), 3);
+        assert_eq!(canvas.get_char(2, 0), 'b');
+        assert_eq!(canvas.put_str(8, 0, "clipped"), 7);
+    }
+
+    #[test]
+    fn test_fullwidth() {
+        let mut canvas = Canvas::new(10, 2).unwrap();
+        assert_eq!(Canvas::str_width("HP\u{3042}e\u{301}"), 5);
+        assert_eq!(Canvas::str_width("\u{5e9}\u{5c1}\u{5b8}\u{5dc}"), 2);
+
+        assert_eq!(canvas.put_str(0, 0, "\u{3042}\u{3044}x"), 5);
+        assert_eq!(canvas.get_char(0, 0), '\u{3042}');
+        assert_eq!(canvas.chars()[1], MAGIC_FULLWIDTH);
+        assert_eq!(canvas.get_char(2, 0), '\u{3044}');
+        assert_eq!(canvas.get_char(4, 0), 'x');
+
+        canvas.put_str(8, 1, "a\u{3042}");
+        assert_eq!(canvas.get_char(8, 1), 'a');
+        assert_eq!(canvas.get_char(9, 1), ' ');
+
+        assert_eq!(canvas.put_char(5, 0, '\u{301}'), 0);
+        assert_eq!(canvas.get_char(5, 0), ' ');
+        assert_eq!(canvas.get_char(4, 0), 'x');
+    }
+
+    #[test]
+    fn test_combining() {
+        let mut canvas = Canvas::new(10, 2).unwrap();
+        assert_eq!(canvas.put_str(0, 0, "\u{304b}\u{3099}e\u{301}x\u{301}!"), 5);
+        assert_eq!(canvas.get_char(0, 0), '\u{304c}');
+        assert_eq!(canvas.chars()[1], MAGIC_FULLWIDTH);
+        assert_eq!(canvas.get_char(2, 0), '\u{e9}');
+        // Marks without a precomposed form are dropped.
+        assert_eq!(canvas.get_char(3, 0), 'x');
+        assert_eq!(canvas.get_char(4, 0), '!');
+
+        // Marks put on their own compose with the character to their left.
+        assert_eq!(canvas.put_char(4, 0, '\u{301}'), 0);
+        assert_eq!(canvas.get_char(3, 0), 'x');
+        canvas.put_char(0, 1, 'a');
+        canvas.put_char(1, 1, '\u{300}');
+        assert_eq!(canvas.get_char(0, 1), '\u{e0}');
+
+        let red = Attr::new(&AnsiColor::Red, &AnsiColor::Black, Style::empty());
+        let blue = Attr::new(&AnsiColor::Blue, &AnsiColor::Black, Style::empty());
+        let cells = [Cell { ch: 'o', attr: red }, Cell { ch: '\u{308}', attr: blue }];
+        assert_eq!(canvas.put_cells(5, 1, &cells), 1);
+        assert_eq!(canvas.cell(5, 1), Some(Cell { ch: '\u{f6}', attr: red }));
+    }
+
+    #[test]
+    fn test_write() {
+        use std::fmt::Write;
+
+        let mut canvas = Canvas::new(10, 3).unwrap();
+        canvas.gotoxy(2, 1);
+        write!(canvas, "HP: {}/{}", 17, 100).unwrap();
+        assert_eq!(canvas.get_char(2, 1), 'H');
+        assert_eq!(canvas.get_char(8, 1), '/');
+        assert_eq!(canvas.get_char(9, 1), '1');
+        assert_eq!(canvas.get_char(0, 2), '0');
+        assert_eq!(canvas.get_char(1, 2), '0');
+        assert_eq!((canvas.wherex(), canvas.wherey()), (2, 2));
+
+        writeln!(canvas, "!").unwrap();
+        assert_eq!(canvas.get_char(2, 2), '!');
+        assert_eq!((canvas.wherex(), canvas.wherey()), (0, 3));
+
+        canvas.gotoxy(9, 0);
+        write!(canvas, "\u{3042}").unwrap();
+        assert_eq!(canvas.get_char(9, 0), ' ');
+        assert_eq!(canvas.get_char(0, 1), '\u{3042}');
+        assert_eq!((canvas.wherex(), canvas.wherey()), (2, 1));
+    }
 }
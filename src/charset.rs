@@ -0,0 +1,602 @@
+//! Conversions between UTF-8, UTF-32, code page 437 and ASCII, using the
+//! same tables as libcaca's importers and exporters.
+
+use std::char;
+use std::cmp::Ordering;
+use std::str;
+use libc::{c_char, c_int, size_t};
+
+extern "C" {
+    fn caca_utf8_to_utf32(s: *const c_char, bytes: *mut size_t) -> u32;
+    fn caca_utf32_to_utf8(buf: *mut c_char, ch: u32) -> size_t;
+    fn caca_utf32_to_cp437(ch: u32) -> u8;
+    fn caca_cp437_to_utf32(ch: u8) -> u32;
+    fn caca_utf32_to_ascii(ch: u32) -> c_char;
+    fn caca_utf32_is_fullwidth(ch: u32) -> c_int;
+}
+
+/// Decodes the first character of a UTF-8 byte sequence, returning it along
+/// with the number of bytes it takes, or `None` if the sequence is
+/// incomplete or is not valid UTF-8, such as stray continuation bytes and
+/// overlong forms.
+pub fn utf8_to_utf32(s: &[u8]) -> Option<(char, usize)> {
+    match s.first() {
+        None    => return None,
+        Some(&0) => return Some(('\0', 1)),
+        Some(_) => (),
+    }
+    // libcaca reads until the sequence is complete or a NUL is found, so the
+    // bytes are copied to a NUL terminated buffer first.
+    let mut buf = [0; 7];
+    let len = s.len().min(6);
+    buf[..len].copy_from_slice(&s[..len]);
+
+    let mut bytes: size_t = 0;
+    let ch = unsafe { caca_utf8_to_utf32(buf.as_ptr() as *const c_char, &mut bytes) };
+    if bytes == 0 {
+        return None;
+    }
+    // libcaca does not validate the sequence, it only decodes it.
+    let bytes = bytes as usize;
+    if str::from_utf8(&s[..bytes]).is_err() {
+        return None;
+    }
+    char::from_u32(ch).map(|c| (c, bytes))
+}
+
+pub fn utf32_to_utf8(c: char) -> String {
+    let mut buf = [0u8; 7];
+    let len = unsafe { caca_utf32_to_utf8(buf.as_mut_ptr() as *mut c_char, c as u32) };
+    String::from_utf8_lossy(&buf[..len as usize]).into_owned()
+}
+
+/// The code page 437 byte for the character, or `?` if there is none.
+pub fn utf32_to_cp437(c: char) -> u8 {
+    unsafe { caca_utf32_to_cp437(c as u32) }
+}
+
+/// The character for a code page 437 byte. Control bytes map to the glyphs
+/// DOS displays for them, so `\n` becomes `\u{25d9}`.
+pub fn cp437_to_utf32(b: u8) -> char {
+    let ch = unsafe { caca_cp437_to_utf32(b) };
+    char::from_u32(ch).unwrap_or(char::REPLACEMENT_CHARACTER)
+}
+
+// The control characters text streams need kept as they are rather than
+// turned into glyphs.
+fn is_stream_control(b: u8) -> bool {
+    matches!(b, b'\t' | b'\n' | b'\r' | 0x1b)
+}
+
+/// An ASCII approximation of the character, such as `+` for box corners, or
+/// `?` if there is none.
+pub fn utf32_to_ascii(c: char) -> char {
+    unsafe { caca_utf32_to_ascii(c as u32) as u8 as char }
+}
+
+/// Decodes a code page 437 byte stream, as found in DOS ANSI art. Tabs, line
+/// breaks and escapes are kept as they are so that the text still reads as
+/// a stream, but escape sequences are not interpreted; import the data with
+/// `ImportFormat::Ansi` to render them.
+pub fn cp437_to_string(data: &[u8]) -> String {
+    data.iter()
+        .map(|&b| if is_stream_control(b) { b as char } else { cp437_to_utf32(b) })
+        .collect()
+}
+
+/// Encodes text as code page 437, keeping tabs, line breaks and escapes as
+/// `cp437_to_string` does.
+pub fn string_to_cp437(s: &str) -> Vec<u8> {
+    s.chars()
+        .map(|c| if c < '\u{80}' && is_stream_control(c as u8) { c as u8 } else { utf32_to_cp437(c) })
+        .collect()
+}
+
+/// Replaces every character of the string with its ASCII approximation, for
+/// terminals without Unicode support.
+pub fn string_to_ascii(s: &str) -> String {
+    s.chars().map(utf32_to_ascii).collect()
+}
+
+/// Whether the character takes two cells, as CJK characters do.
+pub fn is_fullwidth(c: char) -> bool {
+    unsafe { caca_utf32_is_fullwidth(c as u32) != 0 }
+}
+
+/// Whether the character combines with the one before it instead of taking a
+/// cell of its own. These are the nonspacing and enclosing marks and the
+/// invisible format characters (general categories Mn, Me and Cf). Spacing
+/// marks, such as most Devanagari vowel signs, still take a cell.
+pub fn is_combining(c: char) -> bool {
+    let c = c as u32;
+    ZERO_WIDTH.binary_search_by(|&(start, end)| {
+        if end < c {
+            Ordering::Less
+        } else if start > c {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }).is_ok()
+}
+
+/// The number of cells the character takes on a canvas. Since a cell holds a
+/// single character, combining marks take none. Canvases compose them with the
+/// character before them where possible, see `compose`.
+pub fn char_width(c: char) -> usize {
+    if is_combining(c) {
+        0
+    } else if is_fullwidth(c) {
+        2
+    } else {
+        1
+    }
+}
+
+/// The precomposed character for a character followed by a combining mark,
+/// such as `\u{304c}` for `\u{304b}` and U+3099, if Unicode defines one. This
+/// covers the marks from U+0300 to U+036F and the kana voicing marks.
+pub fn compose(c: char, mark: char) -> Option<char> {
+    let key = (c as u32, mark as u32);
+    COMPOSITIONS.binary_search_by(|&(base, mark_, _)| (base, mark_).cmp(&key))
+        .ok()
+        .and_then(|i| char::from_u32(COMPOSITIONS[i].2))
+}
+
+// The inclusive ranges of code points in general categories Mn, Me and Cf, as
+// of Unicode 14.0.
+const ZERO_WIDTH: &[(u32, u32)] = &[
+    (0x000ad, 0x000ad), (0x00300, 0x0036f), (0x00483, 0x00489), (0x00591, 0x005bd),
+    (0x005bf, 0x005bf), (0x005c1, 0x005c2), (0x005c4, 0x005c5), (0x005c7, 0x005c7),
+    (0x00600, 0x00605), (0x00610, 0x0061a), (0x0061c, 0x0061c), (0x0064b, 0x0065f),
+    (0x00670, 0x00670), (0x006d6, 0x006dd), (0x006df, 0x006e4), (0x006e7, 0x006e8),
+    (0x006ea, 0x006ed), (0x0070f, 0x0070f), (0x00711, 0x00711), (0x00730, 0x0074a),
+    (0x007a6, 0x007b0), (0x007eb, 0x007f3), (0x007fd, 0x007fd), (0x00816, 0x00819),
+    (0x0081b, 0x00823), (0x00825, 0x00827), (0x00829, 0x0082d), (0x00859, 0x0085b),
+    (0x00890, 0x00891), (0x00898, 0x0089f), (0x008ca, 0x00902), (0x0093a, 0x0093a),
+    (0x0093c, 0x0093c), (0x00941, 0x00948), (0x0094d, 0x0094d), (0x00951, 0x00957),
+    (0x00962, 0x00963), (0x00981, 0x00981), (0x009bc, 0x009bc), (0x009c1, 0x009c4),
+    (0x009cd, 0x009cd), (0x009e2, 0x009e3), (0x009fe, 0x009fe), (0x00a01, 0x00a02),
+    (0x00a3c, 0x00a3c), (0x00a41, 0x00a42), (0x00a47, 0x00a48), (0x00a4b, 0x00a4d),
+    (0x00a51, 0x00a51), (0x00a70, 0x00a71), (0x00a75, 0x00a75), (0x00a81, 0x00a82),
+    (0x00abc, 0x00abc), (0x00ac1, 0x00ac5), (0x00ac7, 0x00ac8), (0x00acd, 0x00acd),
+    (0x00ae2, 0x00ae3), (0x00afa, 0x00aff), (0x00b01, 0x00b01), (0x00b3c, 0x00b3c),
+    (0x00b3f, 0x00b3f), (0x00b41, 0x00b44), (0x00b4d, 0x00b4d), (0x00b55, 0x00b56),
+    (0x00b62, 0x00b63), (0x00b82, 0x00b82), (0x00bc0, 0x00bc0), (0x00bcd, 0x00bcd),
+    (0x00c00, 0x00c00), (0x00c04, 0x00c04), (0x00c3c, 0x00c3c), (0x00c3e, 0x00c40),
+    (0x00c46, 0x00c48), (0x00c4a, 0x00c4d), (0x00c55, 0x00c56), (0x00c62, 0x00c63),
+    (0x00c81, 0x00c81), (0x00cbc, 0x00cbc), (0x00cbf, 0x00cbf), (0x00cc6, 0x00cc6),
+    (0x00ccc, 0x00ccd), (0x00ce2, 0x00ce3), (0x00d00, 0x00d01), (0x00d3b, 0x00d3c),
+    (0x00d41, 0x00d44), (0x00d4d, 0x00d4d), (0x00d62, 0x00d63), (0x00d81, 0x00d81),
+    (0x00dca, 0x00dca), (0x00dd2, 0x00dd4), (0x00dd6, 0x00dd6), (0x00e31, 0x00e31),
+    (0x00e34, 0x00e3a), (0x00e47, 0x00e4e), (0x00eb1, 0x00eb1), (0x00eb4, 0x00ebc),
+    (0x00ec8, 0x00ecd), (0x00f18, 0x00f19), (0x00f35, 0x00f35), (0x00f37, 0x00f37),
+    (0x00f39, 0x00f39), (0x00f71, 0x00f7e), (0x00f80, 0x00f84), (0x00f86, 0x00f87),
+    (0x00f8d, 0x00f97), (0x00f99, 0x00fbc), (0x00fc6, 0x00fc6), (0x0102d, 0x01030),
+    (0x01032, 0x01037), (0x01039, 0x0103a), (0x0103d, 0x0103e), (0x01058, 0x01059),
+    (0x0105e, 0x01060), (0x01071, 0x01074), (0x01082, 0x01082), (0x01085, 0x01086),
+    (0x0108d, 0x0108d), (0x0109d, 0x0109d), (0x0135d, 0x0135f), (0x01712, 0x01714),
+    (0x01732, 0x01733), (0x01752, 0x01753), (0x01772, 0x01773), (0x017b4, 0x017b5),
+    (0x017b7, 0x017bd), (0x017c6, 0x017c6), (0x017c9, 0x017d3), (0x017dd, 0x017dd),
+    (0x0180b, 0x0180f), (0x01885, 0x01886), (0x018a9, 0x018a9), (0x01920, 0x01922),
+    (0x01927, 0x01928), (0x01932, 0x01932), (0x01939, 0x0193b), (0x01a17, 0x01a18),
+    (0x01a1b, 0x01a1b), (0x01a56, 0x01a56), (0x01a58, 0x01a5e), (0x01a60, 0x01a60),
+    (0x01a62, 0x01a62), (0x01a65, 0x01a6c), (0x01a73, 0x01a7c), (0x01a7f, 0x01a7f),
+    (0x01ab0, 0x01ace), (0x01b00, 0x01b03), (0x01b34, 0x01b34), (0x01b36, 0x01b3a),
+    (0x01b3c, 0x01b3c), (0x01b42, 0x01b42), (0x01b6b, 0x01b73), (0x01b80, 0x01b81),
+    (0x01ba2, 0x01ba5), (0x01ba8, 0x01ba9), (0x01bab, 0x01bad), (0x01be6, 0x01be6),
+    (0x01be8, 0x01be9), (0x01bed, 0x01bed), (0x01bef, 0x01bf1), (0x01c2c, 0x01c33),
+    (0x01c36, 0x01c37), (0x01cd0, 0x01cd2), (0x01cd4, 0x01ce0), (0x01ce2, 0x01ce8),
+    (0x01ced, 0x01ced), (0x01cf4, 0x01cf4), (0x01cf8, 0x01cf9), (0x01dc0, 0x01dff),
+    (0x0200b, 0x0200f), (0x0202a, 0x0202e), (0x02060, 0x02064), (0x02066, 0x0206f),
+    (0x020d0, 0x020f0), (0x02cef, 0x02cf1), (0x02d7f, 0x02d7f), (0x02de0, 0x02dff),
+    (0x0302a, 0x0302d), (0x03099, 0x0309a), (0x0a66f, 0x0a672), (0x0a674, 0x0a67d),
+    (0x0a69e, 0x0a69f), (0x0a6f0, 0x0a6f1), (0x0a802, 0x0a802), (0x0a806, 0x0a806),
+    (0x0a80b, 0x0a80b), (0x0a825, 0x0a826), (0x0a82c, 0x0a82c), (0x0a8c4, 0x0a8c5),
+    (0x0a8e0, 0x0a8f1), (0x0a8ff, 0x0a8ff), (0x0a926, 0x0a92d), (0x0a947, 0x0a951),
+    (0x0a980, 0x0a982), (0x0a9b3, 0x0a9b3), (0x0a9b6, 0x0a9b9), (0x0a9bc, 0x0a9bd),
+    (0x0a9e5, 0x0a9e5), (0x0aa29, 0x0aa2e), (0x0aa31, 0x0aa32), (0x0aa35, 0x0aa36),
+    (0x0aa43, 0x0aa43), (0x0aa4c, 0x0aa4c), (0x0aa7c, 0x0aa7c), (0x0aab0, 0x0aab0),
+    (0x0aab2, 0x0aab4), (0x0aab7, 0x0aab8), (0x0aabe, 0x0aabf), (0x0aac1, 0x0aac1),
+    (0x0aaec, 0x0aaed), (0x0aaf6, 0x0aaf6), (0x0abe5, 0x0abe5), (0x0abe8, 0x0abe8),
+    (0x0abed, 0x0abed), (0x0fb1e, 0x0fb1e), (0x0fe00, 0x0fe0f), (0x0fe20, 0x0fe2f),
+    (0x0feff, 0x0feff), (0x0fff9, 0x0fffb), (0x101fd, 0x101fd), (0x102e0, 0x102e0),
+    (0x10376, 0x1037a), (0x10a01, 0x10a03), (0x10a05, 0x10a06), (0x10a0c, 0x10a0f),
+    (0x10a38, 0x10a3a), (0x10a3f, 0x10a3f), (0x10ae5, 0x10ae6), (0x10d24, 0x10d27),
+    (0x10eab, 0x10eac), (0x10f46, 0x10f50), (0x10f82, 0x10f85), (0x11001, 0x11001),
+    (0x11038, 0x11046), (0x11070, 0x11070), (0x11073, 0x11074), (0x1107f, 0x11081),
+    (0x110b3, 0x110b6), (0x110b9, 0x110ba), (0x110bd, 0x110bd), (0x110c2, 0x110c2),
+    (0x110cd, 0x110cd), (0x11100, 0x11102), (0x11127, 0x1112b), (0x1112d, 0x11134),
+    (0x11173, 0x11173), (0x11180, 0x11181), (0x111b6, 0x111be), (0x111c9, 0x111cc),
+    (0x111cf, 0x111cf), (0x1122f, 0x11231), (0x11234, 0x11234), (0x11236, 0x11237),
+    (0x1123e, 0x1123e), (0x112df, 0x112df), (0x112e3, 0x112ea), (0x11300, 0x11301),
+    (0x1133b, 0x1133c), (0x11340, 0x11340), (0x11366, 0x1136c), (0x11370, 0x11374),
+    (0x11438, 0x1143f), (0x11442, 0x11444), (0x11446, 0x11446), (0x1145e, 0x1145e),
+    (0x114b3, 0x114b8), (0x114ba, 0x114ba), (0x114bf, 0x114c0), (0x114c2, 0x114c3),
+    (0x115b2, 0x115b5), (0x115bc, 0x115bd), (0x115bf, 0x115c0), (0x115dc, 0x115dd),
+    (0x11633, 0x1163a), (0x1163d, 0x1163d), (0x1163f, 0x11640), (0x116ab, 0x116ab),
+    (0x116ad, 0x116ad), (0x116b0, 0x116b5), (0x116b7, 0x116b7), (0x1171d, 0x1171f),
+    (0x11722, 0x11725), (0x11727, 0x1172b), (0x1182f, 0x11837), (0x11839, 0x1183a),
+    (0x1193b, 0x1193c), (0x1193e, 0x1193e), (0x11943, 0x11943), (0x119d4, 0x119d7),
+    (0x119da, 0x119db), (0x119e0, 0x119e0), (0x11a01, 0x11a0a), (0x11a33, 0x11a38),
+    (0x11a3b, 0x11a3e), (0x11a47, 0x11a47), (0x11a51, 0x11a56), (0x11a59, 0x11a5b),
+    (0x11a8a, 0x11a96), (0x11a98, 0x11a99), (0x11c30, 0x11c36), (0x11c38, 0x11c3d),
+    (0x11c3f, 0x11c3f), (0x11c92, 0x11ca7), (0x11caa, 0x11cb0), (0x11cb2, 0x11cb3),
+    (0x11cb5, 0x11cb6), (0x11d31, 0x11d36), (0x11d3a, 0x11d3a), (0x11d3c, 0x11d3d),
+    (0x11d3f, 0x11d45), (0x11d47, 0x11d47), (0x11d90, 0x11d91), (0x11d95, 0x11d95),
+    (0x11d97, 0x11d97), (0x11ef3, 0x11ef4), (0x13430, 0x13438), (0x16af0, 0x16af4),
+    (0x16b30, 0x16b36), (0x16f4f, 0x16f4f), (0x16f8f, 0x16f92), (0x16fe4, 0x16fe4),
+    (0x1bc9d, 0x1bc9e), (0x1bca0, 0x1bca3), (0x1cf00, 0x1cf2d), (0x1cf30, 0x1cf46),
+    (0x1d167, 0x1d169), (0x1d173, 0x1d182), (0x1d185, 0x1d18b), (0x1d1aa, 0x1d1ad),
+    (0x1d242, 0x1d244), (0x1da00, 0x1da36), (0x1da3b, 0x1da6c), (0x1da75, 0x1da75),
+    (0x1da84, 0x1da84), (0x1da9b, 0x1da9f), (0x1daa1, 0x1daaf), (0x1e000, 0x1e006),
+    (0x1e008, 0x1e018), (0x1e01b, 0x1e021), (0x1e023, 0x1e024), (0x1e026, 0x1e02a),
+    (0x1e130, 0x1e136), (0x1e2ae, 0x1e2ae), (0x1e2ec, 0x1e2ef), (0x1e8d0, 0x1e8d6),
+    (0x1e944, 0x1e94a), (0xe0001, 0xe0001), (0xe0020, 0xe007f), (0xe0100, 0xe01ef),
+];
+
+// The canonical compositions of a base character and a mark from U+0300 to
+// U+036F, U+3099 or U+309A, sorted by base and mark, as of Unicode 14.0.
+const COMPOSITIONS: &[(u32, u32, u32)] = &[
+    (0x003c, 0x0338, 0x226e), (0x003d, 0x0338, 0x2260), (0x003e, 0x0338, 0x226f),
+    (0x0041, 0x0300, 0x00c0), (0x0041, 0x0301, 0x00c1), (0x0041, 0x0302, 0x00c2),
+    (0x0041, 0x0303, 0x00c3), (0x0041, 0x0304, 0x0100), (0x0041, 0x0306, 0x0102),
+    (0x0041, 0x0307, 0x0226), (0x0041, 0x0308, 0x00c4), (0x0041, 0x0309, 0x1ea2),
+    (0x0041, 0x030a, 0x00c5), (0x0041, 0x030c, 0x01cd), (0x0041, 0x030f, 0x0200),
+    (0x0041, 0x0311, 0x0202), (0x0041, 0x0323, 0x1ea0), (0x0041, 0x0325, 0x1e00),
+    (0x0041, 0x0328, 0x0104), (0x0042, 0x0307, 0x1e02), (0x0042, 0x0323, 0x1e04),
+    (0x0042, 0x0331, 0x1e06), (0x0043, 0x0301, 0x0106), (0x0043, 0x0302, 0x0108),
+    (0x0043, 0x0307, 0x010a), (0x0043, 0x030c, 0x010c), (0x0043, 0x0327, 0x00c7),
+    (0x0044, 0x0307, 0x1e0a), (0x0044, 0x030c, 0x010e), (0x0044, 0x0323, 0x1e0c),
+    (0x0044, 0x0327, 0x1e10), (0x0044, 0x032d, 0x1e12), (0x0044, 0x0331, 0x1e0e),
+    (0x0045, 0x0300, 0x00c8), (0x0045, 0x0301, 0x00c9), (0x0045, 0x0302, 0x00ca),
+    (0x0045, 0x0303, 0x1ebc), (0x0045, 0x0304, 0x0112), (0x0045, 0x0306, 0x0114),
+    (0x0045, 0x0307, 0x0116), (0x0045, 0x0308, 0x00cb), (0x0045, 0x0309, 0x1eba),
+    (0x0045, 0x030c, 0x011a), (0x0045, 0x030f, 0x0204), (0x0045, 0x0311, 0x0206),
+    (0x0045, 0x0323, 0x1eb8), (0x0045, 0x0327, 0x0228), (0x0045, 0x0328, 0x0118),
+    (0x0045, 0x032d, 0x1e18), (0x0045, 0x0330, 0x1e1a), (0x0046, 0x0307, 0x1e1e),
+    (0x0047, 0x0301, 0x01f4), (0x0047, 0x0302, 0x011c), (0x0047, 0x0304, 0x1e20),
+    (0x0047, 0x0306, 0x011e), (0x0047, 0x0307, 0x0120), (0x0047, 0x030c, 0x01e6),
+    (0x0047, 0x0327, 0x0122), (0x0048, 0x0302, 0x0124), (0x0048, 0x0307, 0x1e22),
+    (0x0048, 0x0308, 0x1e26), (0x0048, 0x030c, 0x021e), (0x0048, 0x0323, 0x1e24),
+    (0x0048, 0x0327, 0x1e28), (0x0048, 0x032e, 0x1e2a), (0x0049, 0x0300, 0x00cc),
+    (0x0049, 0x0301, 0x00cd), (0x0049, 0x0302, 0x00ce), (0x0049, 0x0303, 0x0128),
+    (0x0049, 0x0304, 0x012a), (0x0049, 0x0306, 0x012c), (0x0049, 0x0307, 0x0130),
+    (0x0049, 0x0308, 0x00cf), (0x0049, 0x0309, 0x1ec8), (0x0049, 0x030c, 0x01cf),
+    (0x0049, 0x030f, 0x0208), (0x0049, 0x0311, 0x020a), (0x0049, 0x0323, 0x1eca),
+    (0x0049, 0x0328, 0x012e), (0x0049, 0x0330, 0x1e2c), (0x004a, 0x0302, 0x0134),
+    (0x004b, 0x0301, 0x1e30), (0x004b, 0x030c, 0x01e8), (0x004b, 0x0323, 0x1e32),
+    (0x004b, 0x0327, 0x0136), (0x004b, 0x0331, 0x1e34), (0x004c, 0x0301, 0x0139),
+    (0x004c, 0x030c, 0x013d), (0x004c, 0x0323, 0x1e36), (0x004c, 0x0327, 0x013b),
+    (0x004c, 0x032d, 0x1e3c), (0x004c, 0x0331, 0x1e3a), (0x004d, 0x0301, 0x1e3e),
+    (0x004d, 0x0307, 0x1e40), (0x004d, 0x0323, 0x1e42), (0x004e, 0x0300, 0x01f8),
+    (0x004e, 0x0301, 0x0143), (0x004e, 0x0303, 0x00d1), (0x004e, 0x0307, 0x1e44),
+    (0x004e, 0x030c, 0x0147), (0x004e, 0x0323, 0x1e46), (0x004e, 0x0327, 0x0145),
+    (0x004e, 0x032d, 0x1e4a), (0x004e, 0x0331, 0x1e48), (0x004f, 0x0300, 0x00d2),
+    (0x004f, 0x0301, 0x00d3), (0x004f, 0x0302, 0x00d4), (0x004f, 0x0303, 0x00d5),
+    (0x004f, 0x0304, 0x014c), (0x004f, 0x0306, 0x014e), (0x004f, 0x0307, 0x022e),
+    (0x004f, 0x0308, 0x00d6), (0x004f, 0x0309, 0x1ece), (0x004f, 0x030b, 0x0150),
+    (0x004f, 0x030c, 0x01d1), (0x004f, 0x030f, 0x020c), (0x004f, 0x0311, 0x020e),
+    (0x004f, 0x031b, 0x01a0), (0x004f, 0x0323, 0x1ecc), (0x004f, 0x0328, 0x01ea),
+    (0x0050, 0x0301, 0x1e54), (0x0050, 0x0307, 0x1e56), (0x0052, 0x0301, 0x0154),
+    (0x0052, 0x0307, 0x1e58), (0x0052, 0x030c, 0x0158), (0x0052, 0x030f, 0x0210),
+    (0x0052, 0x0311, 0x0212), (0x0052, 0x0323, 0x1e5a), (0x0052, 0x0327, 0x0156),
+    (0x0052, 0x0331, 0x1e5e), (0x0053, 0x0301, 0x015a), (0x0053, 0x0302, 0x015c),
+    (0x0053, 0x0307, 0x1e60), (0x0053, 0x030c, 0x0160), (0x0053, 0x0323, 0x1e62),
+    (0x0053, 0x0326, 0x0218), (0x0053, 0x0327, 0x015e), (0x0054, 0x0307, 0x1e6a),
+    (0x0054, 0x030c, 0x0164), (0x0054, 0x0323, 0x1e6c), (0x0054, 0x0326, 0x021a),
+    (0x0054, 0x0327, 0x0162), (0x0054, 0x032d, 0x1e70), (0x0054, 0x0331, 0x1e6e),
+    (0x0055, 0x0300, 0x00d9), (0x0055, 0x0301, 0x00da), (0x0055, 0x0302, 0x00db),
+    (0x0055, 0x0303, 0x0168), (0x0055, 0x0304, 0x016a), (0x0055, 0x0306, 0x016c),
+    (0x0055, 0x0308, 0x00dc), (0x0055, 0x0309, 0x1ee6), (0x0055, 0x030a, 0x016e),
+    (0x0055, 0x030b, 0x0170), (0x0055, 0x030c, 0x01d3), (0x0055, 0x030f, 0x0214),
+    (0x0055, 0x0311, 0x0216), (0x0055, 0x031b, 0x01af), (0x0055, 0x0323, 0x1ee4),
+    (0x0055, 0x0324, 0x1e72), (0x0055, 0x0328, 0x0172), (0x0055, 0x032d, 0x1e76),
+    (0x0055, 0x0330, 0x1e74), (0x0056, 0x0303, 0x1e7c), (0x0056, 0x0323, 0x1e7e),
+    (0x0057, 0x0300, 0x1e80), (0x0057, 0x0301, 0x1e82), (0x0057, 0x0302, 0x0174),
+    (0x0057, 0x0307, 0x1e86), (0x0057, 0x0308, 0x1e84), (0x0057, 0x0323, 0x1e88),
+    (0x0058, 0x0307, 0x1e8a), (0x0058, 0x0308, 0x1e8c), (0x0059, 0x0300, 0x1ef2),
+    (0x0059, 0x0301, 0x00dd), (0x0059, 0x0302, 0x0176), (0x0059, 0x0303, 0x1ef8),
+    (0x0059, 0x0304, 0x0232), (0x0059, 0x0307, 0x1e8e), (0x0059, 0x0308, 0x0178),
+    (0x0059, 0x0309, 0x1ef6), (0x0059, 0x0323, 0x1ef4), (0x005a, 0x0301, 0x0179),
+    (0x005a, 0x0302, 0x1e90), (0x005a, 0x0307, 0x017b), (0x005a, 0x030c, 0x017d),
+    (0x005a, 0x0323, 0x1e92), (0x005a, 0x0331, 0x1e94), (0x0061, 0x0300, 0x00e0),
+    (0x0061, 0x0301, 0x00e1), (0x0061, 0x0302, 0x00e2), (0x0061, 0x0303, 0x00e3),
+    (0x0061, 0x0304, 0x0101), (0x0061, 0x0306, 0x0103), (0x0061, 0x0307, 0x0227),
+    (0x0061, 0x0308, 0x00e4), (0x0061, 0x0309, 0x1ea3), (0x0061, 0x030a, 0x00e5),
+    (0x0061, 0x030c, 0x01ce), (0x0061, 0x030f, 0x0201), (0x0061, 0x0311, 0x0203),
+    (0x0061, 0x0323, 0x1ea1), (0x0061, 0x0325, 0x1e01), (0x0061, 0x0328, 0x0105),
+    (0x0062, 0x0307, 0x1e03), (0x0062, 0x0323, 0x1e05), (0x0062, 0x0331, 0x1e07),
+    (0x0063, 0x0301, 0x0107), (0x0063, 0x0302, 0x0109), (0x0063, 0x0307, 0x010b),
+    (0x0063, 0x030c, 0x010d), (0x0063, 0x0327, 0x00e7), (0x0064, 0x0307, 0x1e0b),
+    (0x0064, 0x030c, 0x010f), (0x0064, 0x0323, 0x1e0d), (0x0064, 0x0327, 0x1e11),
+    (0x0064, 0x032d, 0x1e13), (0x0064, 0x0331, 0x1e0f), (0x0065, 0x0300, 0x00e8),
+    (0x0065, 0x0301, 0x00e9), (0x0065, 0x0302, 0x00ea), (0x0065, 0x0303, 0x1ebd),
+    (0x0065, 0x0304, 0x0113), (0x0065, 0x0306, 0x0115), (0x0065, 0x0307, 0x0117),
+    (0x0065, 0x0308, 0x00eb), (0x0065, 0x0309, 0x1ebb), (0x0065, 0x030c, 0x011b),
+    (0x0065, 0x030f, 0x0205), (0x0065, 0x0311, 0x0207), (0x0065, 0x0323, 0x1eb9),
+    (0x0065, 0x0327, 0x0229), (0x0065, 0x0328, 0x0119), (0x0065, 0x032d, 0x1e19),
+    (0x0065, 0x0330, 0x1e1b), (0x0066, 0x0307, 0x1e1f), (0x0067, 0x0301, 0x01f5),
+    (0x0067, 0x0302, 0x011d), (0x0067, 0x0304, 0x1e21), (0x0067, 0x0306, 0x011f),
+    (0x0067, 0x0307, 0x0121), (0x0067, 0x030c, 0x01e7), (0x0067, 0x0327, 0x0123),
+    (0x0068, 0x0302, 0x0125), (0x0068, 0x0307, 0x1e23), (0x0068, 0x0308, 0x1e27),
+    (0x0068, 0x030c, 0x021f), (0x0068, 0x0323, 0x1e25), (0x0068, 0x0327, 0x1e29),
+    (0x0068, 0x032e, 0x1e2b), (0x0068, 0x0331, 0x1e96), (0x0069, 0x0300, 0x00ec),
+    (0x0069, 0x0301, 0x00ed), (0x0069, 0x0302, 0x00ee), (0x0069, 0x0303, 0x0129),
+    (0x0069, 0x0304, 0x012b), (0x0069, 0x0306, 0x012d), (0x0069, 0x0308, 0x00ef),
+    (0x0069, 0x0309, 0x1ec9), (0x0069, 0x030c, 0x01d0), (0x0069, 0x030f, 0x0209),
+    (0x0069, 0x0311, 0x020b), (0x0069, 0x0323, 0x1ecb), (0x0069, 0x0328, 0x012f),
+    (0x0069, 0x0330, 0x1e2d), (0x006a, 0x0302, 0x0135), (0x006a, 0x030c, 0x01f0),
+    (0x006b, 0x0301, 0x1e31), (0x006b, 0x030c, 0x01e9), (0x006b, 0x0323, 0x1e33),
+    (0x006b, 0x0327, 0x0137), (0x006b, 0x0331, 0x1e35), (0x006c, 0x0301, 0x013a),
+    (0x006c, 0x030c, 0x013e), (0x006c, 0x0323, 0x1e37), (0x006c, 0x0327, 0x013c),
+    (0x006c, 0x032d, 0x1e3d), (0x006c, 0x0331, 0x1e3b), (0x006d, 0x0301, 0x1e3f),
+    (0x006d, 0x0307, 0x1e41), (0x006d, 0x0323, 0x1e43), (0x006e, 0x0300, 0x01f9),
+    (0x006e, 0x0301, 0x0144), (0x006e, 0x0303, 0x00f1), (0x006e, 0x0307, 0x1e45),
+    (0x006e, 0x030c, 0x0148), (0x006e, 0x0323, 0x1e47), (0x006e, 0x0327, 0x0146),
+    (0x006e, 0x032d, 0x1e4b), (0x006e, 0x0331, 0x1e49), (0x006f, 0x0300, 0x00f2),
+    (0x006f, 0x0301, 0x00f3), (0x006f, 0x0302, 0x00f4), (0x006f, 0x0303, 0x00f5),
+    (0x006f, 0x0304, 0x014d), (0x006f, 0x0306, 0x014f), (0x006f, 0x0307, 0x022f),
+    (0x006f, 0x0308, 0x00f6), (0x006f, 0x0309, 0x1ecf), (0x006f, 0x030b, 0x0151),
+    (0x006f, 0x030c, 0x01d2), (0x006f, 0x030f, 0x020d), (0x006f, 0x0311, 0x020f),
+    (0x006f, 0x031b, 0x01a1), (0x006f, 0x0323, 0x1ecd), (0x006f, 0x0328, 0x01eb),
+    (0x0070, 0x0301, 0x1e55), (0x0070, 0x0307, 0x1e57), (0x0072, 0x0301, 0x0155),
+    (0x0072, 0x0307, 0x1e59), (0x0072, 0x030c, 0x0159), (0x0072, 0x030f, 0x0211),
+    (0x0072, 0x0311, 0x0213), (0x0072, 0x0323, 0x1e5b), (0x0072, 0x0327, 0x0157),
+    (0x0072, 0x0331, 0x1e5f), (0x0073, 0x0301, 0x015b), (0x0073, 0x0302, 0x015d),
+    (0x0073, 0x0307, 0x1e61), (0x0073, 0x030c, 0x0161), (0x0073, 0x0323, 0x1e63),
+    (0x0073, 0x0326, 0x0219), (0x0073, 0x0327, 0x015f), (0x0074, 0x0307, 0x1e6b),
+    (0x0074, 0x0308, 0x1e97), (0x0074, 0x030c, 0x0165), (0x0074, 0x0323, 0x1e6d),
+    (0x0074, 0x0326, 0x021b), (0x0074, 0x0327, 0x0163), (0x0074, 0x032d, 0x1e71),
+    (0x0074, 0x0331, 0x1e6f), (0x0075, 0x0300, 0x00f9), (0x0075, 0x0301, 0x00fa),
+    (0x0075, 0x0302, 0x00fb), (0x0075, 0x0303, 0x0169), (0x0075, 0x0304, 0x016b),
+    (0x0075, 0x0306, 0x016d), (0x0075, 0x0308, 0x00fc), (0x0075, 0x0309, 0x1ee7),
+    (0x0075, 0x030a, 0x016f), (0x0075, 0x030b, 0x0171), (0x0075, 0x030c, 0x01d4),
+    (0x0075, 0x030f, 0x0215), (0x0075, 0x0311, 0x0217), (0x0075, 0x031b, 0x01b0),
+    (0x0075, 0x0323, 0x1ee5), (0x0075, 0x0324, 0x1e73), (0x0075, 0x0328, 0x0173),
+    (0x0075, 0x032d, 0x1e77), (0x0075, 0x0330, 0x1e75), (0x0076, 0x0303, 0x1e7d),
+    (0x0076, 0x0323, 0x1e7f), (0x0077, 0x0300, 0x1e81), (0x0077, 0x0301, 0x1e83),
+    (0x0077, 0x0302, 0x0175), (0x0077, 0x0307, 0x1e87), (0x0077, 0x0308, 0x1e85),
+    (0x0077, 0x030a, 0x1e98), (0x0077, 0x0323, 0x1e89), (0x0078, 0x0307, 0x1e8b),
+    (0x0078, 0x0308, 0x1e8d), (0x0079, 0x0300, 0x1ef3), (0x0079, 0x0301, 0x00fd),
+    (0x0079, 0x0302, 0x0177), (0x0079, 0x0303, 0x1ef9), (0x0079, 0x0304, 0x0233),
+    (0x0079, 0x0307, 0x1e8f), (0x0079, 0x0308, 0x00ff), (0x0079, 0x0309, 0x1ef7),
+    (0x0079, 0x030a, 0x1e99), (0x0079, 0x0323, 0x1ef5), (0x007a, 0x0301, 0x017a),
+    (0x007a, 0x0302, 0x1e91), (0x007a, 0x0307, 0x017c), (0x007a, 0x030c, 0x017e),
+    (0x007a, 0x0323, 0x1e93), (0x007a, 0x0331, 0x1e95), (0x00a8, 0x0300, 0x1fed),
+    (0x00a8, 0x0301, 0x0385), (0x00a8, 0x0342, 0x1fc1), (0x00c2, 0x0300, 0x1ea6),
+    (0x00c2, 0x0301, 0x1ea4), (0x00c2, 0x0303, 0x1eaa), (0x00c2, 0x0309, 0x1ea8),
+    (0x00c4, 0x0304, 0x01de), (0x00c5, 0x0301, 0x01fa), (0x00c6, 0x0301, 0x01fc),
+    (0x00c6, 0x0304, 0x01e2), (0x00c7, 0x0301, 0x1e08), (0x00ca, 0x0300, 0x1ec0),
+    (0x00ca, 0x0301, 0x1ebe), (0x00ca, 0x0303, 0x1ec4), (0x00ca, 0x0309, 0x1ec2),
+    (0x00cf, 0x0301, 0x1e2e), (0x00d4, 0x0300, 0x1ed2), (0x00d4, 0x0301, 0x1ed0),
+    (0x00d4, 0x0303, 0x1ed6), (0x00d4, 0x0309, 0x1ed4), (0x00d5, 0x0301, 0x1e4c),
+    (0x00d5, 0x0304, 0x022c), (0x00d5, 0x0308, 0x1e4e), (0x00d6, 0x0304, 0x022a),
+    (0x00d8, 0x0301, 0x01fe), (0x00dc, 0x0300, 0x01db), (0x00dc, 0x0301, 0x01d7),
+    (0x00dc, 0x0304, 0x01d5), (0x00dc, 0x030c, 0x01d9), (0x00e2, 0x0300, 0x1ea7),
+    (0x00e2, 0x0301, 0x1ea5), (0x00e2, 0x0303, 0x1eab), (0x00e2, 0x0309, 0x1ea9),
+    (0x00e4, 0x0304, 0x01df), (0x00e5, 0x0301, 0x01fb), (0x00e6, 0x0301, 0x01fd),
+    (0x00e6, 0x0304, 0x01e3), (0x00e7, 0x0301, 0x1e09), (0x00ea, 0x0300, 0x1ec1),
+    (0x00ea, 0x0301, 0x1ebf), (0x00ea, 0x0303, 0x1ec5), (0x00ea, 0x0309, 0x1ec3),
+    (0x00ef, 0x0301, 0x1e2f), (0x00f4, 0x0300, 0x1ed3), (0x00f4, 0x0301, 0x1ed1),
+    (0x00f4, 0x0303, 0x1ed7), (0x00f4, 0x0309, 0x1ed5), (0x00f5, 0x0301, 0x1e4d),
+    (0x00f5, 0x0304, 0x022d), (0x00f5, 0x0308, 0x1e4f), (0x00f6, 0x0304, 0x022b),
+    (0x00f8, 0x0301, 0x01ff), (0x00fc, 0x0300, 0x01dc), (0x00fc, 0x0301, 0x01d8),
+    (0x00fc, 0x0304, 0x01d6), (0x00fc, 0x030c, 0x01da), (0x0102, 0x0300, 0x1eb0),
+    (0x0102, 0x0301, 0x1eae), (0x0102, 0x0303, 0x1eb4), (0x0102, 0x0309, 0x1eb2),
+    (0x0103, 0x0300, 0x1eb1), (0x0103, 0x0301, 0x1eaf), (0x0103, 0x0303, 0x1eb5),
+    (0x0103, 0x0309, 0x1eb3), (0x0112, 0x0300, 0x1e14), (0x0112, 0x0301, 0x1e16),
+    (0x0113, 0x0300, 0x1e15), (0x0113, 0x0301, 0x1e17), (0x014c, 0x0300, 0x1e50),
+    (0x014c, 0x0301, 0x1e52), (0x014d, 0x0300, 0x1e51), (0x014d, 0x0301, 0x1e53),
+    (0x015a, 0x0307, 0x1e64), (0x015b, 0x0307, 0x1e65), (0x0160, 0x0307, 0x1e66),
+    (0x0161, 0x0307, 0x1e67), (0x0168, 0x0301, 0x1e78), (0x0169, 0x0301, 0x1e79),
+    (0x016a, 0x0308, 0x1e7a), (0x016b, 0x0308, 0x1e7b), (0x017f, 0x0307, 0x1e9b),
+    (0x01a0, 0x0300, 0x1edc), (0x01a0, 0x0301, 0x1eda), (0x01a0, 0x0303, 0x1ee0),
+    (0x01a0, 0x0309, 0x1ede), (0x01a0, 0x0323, 0x1ee2), (0x01a1, 0x0300, 0x1edd),
+    (0x01a1, 0x0301, 0x1edb), (0x01a1, 0x0303, 0x1ee1), (0x01a1, 0x0309, 0x1edf),
+    (0x01a1, 0x0323, 0x1ee3), (0x01af, 0x0300, 0x1eea), (0x01af, 0x0301, 0x1ee8),
+    (0x01af, 0x0303, 0x1eee), (0x01af, 0x0309, 0x1eec), (0x01af, 0x0323, 0x1ef0),
+    (0x01b0, 0x0300, 0x1eeb), (0x01b0, 0x0301, 0x1ee9), (0x01b0, 0x0303, 0x1eef),
+    (0x01b0, 0x0309, 0x1eed), (0x01b0, 0x0323, 0x1ef1), (0x01b7, 0x030c, 0x01ee),
+    (0x01ea, 0x0304, 0x01ec), (0x01eb, 0x0304, 0x01ed), (0x0226, 0x0304, 0x01e0),
+    (0x0227, 0x0304, 0x01e1), (0x0228, 0x0306, 0x1e1c), (0x0229, 0x0306, 0x1e1d),
+    (0x022e, 0x0304, 0x0230), (0x022f, 0x0304, 0x0231), (0x0292, 0x030c, 0x01ef),
+    (0x0391, 0x0300, 0x1fba), (0x0391, 0x0301, 0x0386), (0x0391, 0x0304, 0x1fb9),
+    (0x0391, 0x0306, 0x1fb8), (0x0391, 0x0313, 0x1f08), (0x0391, 0x0314, 0x1f09),
+    (0x0391, 0x0345, 0x1fbc), (0x0395, 0x0300, 0x1fc8), (0x0395, 0x0301, 0x0388),
+    (0x0395, 0x0313, 0x1f18), (0x0395, 0x0314, 0x1f19), (0x0397, 0x0300, 0x1fca),
+    (0x0397, 0x0301, 0x0389), (0x0397, 0x0313, 0x1f28), (0x0397, 0x0314, 0x1f29),
+    (0x0397, 0x0345, 0x1fcc), (0x0399, 0x0300, 0x1fda), (0x0399, 0x0301, 0x038a),
+    (0x0399, 0x0304, 0x1fd9), (0x0399, 0x0306, 0x1fd8), (0x0399, 0x0308, 0x03aa),
+    (0x0399, 0x0313, 0x1f38), (0x0399, 0x0314, 0x1f39), (0x039f, 0x0300, 0x1ff8),
+    (0x039f, 0x0301, 0x038c), (0x039f, 0x0313, 0x1f48), (0x039f, 0x0314, 0x1f49),
+    (0x03a1, 0x0314, 0x1fec), (0x03a5, 0x0300, 0x1fea), (0x03a5, 0x0301, 0x038e),
+    (0x03a5, 0x0304, 0x1fe9), (0x03a5, 0x0306, 0x1fe8), (0x03a5, 0x0308, 0x03ab),
+    (0x03a5, 0x0314, 0x1f59), (0x03a9, 0x0300, 0x1ffa), (0x03a9, 0x0301, 0x038f),
+    (0x03a9, 0x0313, 0x1f68), (0x03a9, 0x0314, 0x1f69), (0x03a9, 0x0345, 0x1ffc),
+    (0x03ac, 0x0345, 0x1fb4), (0x03ae, 0x0345, 0x1fc4), (0x03b1, 0x0300, 0x1f70),
+    (0x03b1, 0x0301, 0x03ac), (0x03b1, 0x0304, 0x1fb1), (0x03b1, 0x0306, 0x1fb0),
+    (0x03b1, 0x0313, 0x1f00), (0x03b1, 0x0314, 0x1f01), (0x03b1, 0x0342, 0x1fb6),
+    (0x03b1, 0x0345, 0x1fb3), (0x03b5, 0x0300, 0x1f72), (0x03b5, 0x0301, 0x03ad),
+    (0x03b5, 0x0313, 0x1f10), (0x03b5, 0x0314, 0x1f11), (0x03b7, 0x0300, 0x1f74),
+    (0x03b7, 0x0301, 0x03ae), (0x03b7, 0x0313, 0x1f20), (0x03b7, 0x0314, 0x1f21),
+    (0x03b7, 0x0342, 0x1fc6), (0x03b7, 0x0345, 0x1fc3), (0x03b9, 0x0300, 0x1f76),
+    (0x03b9, 0x0301, 0x03af), (0x03b9, 0x0304, 0x1fd1), (0x03b9, 0x0306, 0x1fd0),
+    (0x03b9, 0x0308, 0x03ca), (0x03b9, 0x0313, 0x1f30), (0x03b9, 0x0314, 0x1f31),
+    (0x03b9, 0x0342, 0x1fd6), (0x03bf, 0x0300, 0x1f78), (0x03bf, 0x0301, 0x03cc),
+    (0x03bf, 0x0313, 0x1f40), (0x03bf, 0x0314, 0x1f41), (0x03c1, 0x0313, 0x1fe4),
+    (0x03c1, 0x0314, 0x1fe5), (0x03c5, 0x0300, 0x1f7a), (0x03c5, 0x0301, 0x03cd),
+    (0x03c5, 0x0304, 0x1fe1), (0x03c5, 0x0306, 0x1fe0), (0x03c5, 0x0308, 0x03cb),
+    (0x03c5, 0x0313, 0x1f50), (0x03c5, 0x0314, 0x1f51), (0x03c5, 0x0342, 0x1fe6),
+    (0x03c9, 0x0300, 0x1f7c), (0x03c9, 0x0301, 0x03ce), (0x03c9, 0x0313, 0x1f60),
+    (0x03c9, 0x0314, 0x1f61), (0x03c9, 0x0342, 0x1ff6), (0x03c9, 0x0345, 0x1ff3),
+    (0x03ca, 0x0300, 0x1fd2), (0x03ca, 0x0301, 0x0390), (0x03ca, 0x0342, 0x1fd7),
+    (0x03cb, 0x0300, 0x1fe2), (0x03cb, 0x0301, 0x03b0), (0x03cb, 0x0342, 0x1fe7),
+    (0x03ce, 0x0345, 0x1ff4), (0x03d2, 0x0301, 0x03d3), (0x03d2, 0x0308, 0x03d4),
+    (0x0406, 0x0308, 0x0407), (0x0410, 0x0306, 0x04d0), (0x0410, 0x0308, 0x04d2),
+    (0x0413, 0x0301, 0x0403), (0x0415, 0x0300, 0x0400), (0x0415, 0x0306, 0x04d6),
+    (0x0415, 0x0308, 0x0401), (0x0416, 0x0306, 0x04c1), (0x0416, 0x0308, 0x04dc),
+    (0x0417, 0x0308, 0x04de), (0x0418, 0x0300, 0x040d), (0x0418, 0x0304, 0x04e2),
+    (0x0418, 0x0306, 0x0419), (0x0418, 0x0308, 0x04e4), (0x041a, 0x0301, 0x040c),
+    (0x041e, 0x0308, 0x04e6), (0x0423, 0x0304, 0x04ee), (0x0423, 0x0306, 0x040e),
+    (0x0423, 0x0308, 0x04f0), (0x0423, 0x030b, 0x04f2), (0x0427, 0x0308, 0x04f4),
+    (0x042b, 0x0308, 0x04f8), (0x042d, 0x0308, 0x04ec), (0x0430, 0x0306, 0x04d1),
+    (0x0430, 0x0308, 0x04d3), (0x0433, 0x0301, 0x0453), (0x0435, 0x0300, 0x0450),
+    (0x0435, 0x0306, 0x04d7), (0x0435, 0x0308, 0x0451), (0x0436, 0x0306, 0x04c2),
+    (0x0436, 0x0308, 0x04dd), (0x0437, 0x0308, 0x04df), (0x0438, 0x0300, 0x045d),
+    (0x0438, 0x0304, 0x04e3), (0x0438, 0x0306, 0x0439), (0x0438, 0x0308, 0x04e5),
+    (0x043a, 0x0301, 0x045c), (0x043e, 0x0308, 0x04e7), (0x0443, 0x0304, 0x04ef),
+    (0x0443, 0x0306, 0x045e), (0x0443, 0x0308, 0x04f1), (0x0443, 0x030b, 0x04f3),
+    (0x0447, 0x0308, 0x04f5), (0x044b, 0x0308, 0x04f9), (0x044d, 0x0308, 0x04ed),
+    (0x0456, 0x0308, 0x0457), (0x0474, 0x030f, 0x0476), (0x0475, 0x030f, 0x0477),
+    (0x04d8, 0x0308, 0x04da), (0x04d9, 0x0308, 0x04db), (0x04e8, 0x0308, 0x04ea),
+    (0x04e9, 0x0308, 0x04eb), (0x1e36, 0x0304, 0x1e38), (0x1e37, 0x0304, 0x1e39),
+    (0x1e5a, 0x0304, 0x1e5c), (0x1e5b, 0x0304, 0x1e5d), (0x1e62, 0x0307, 0x1e68),
+    (0x1e63, 0x0307, 0x1e69), (0x1ea0, 0x0302, 0x1eac), (0x1ea0, 0x0306, 0x1eb6),
+    (0x1ea1, 0x0302, 0x1ead), (0x1ea1, 0x0306, 0x1eb7), (0x1eb8, 0x0302, 0x1ec6),
+    (0x1eb9, 0x0302, 0x1ec7), (0x1ecc, 0x0302, 0x1ed8), (0x1ecd, 0x0302, 0x1ed9),
+    (0x1f00, 0x0300, 0x1f02), (0x1f00, 0x0301, 0x1f04), (0x1f00, 0x0342, 0x1f06),
+    (0x1f00, 0x0345, 0x1f80), (0x1f01, 0x0300, 0x1f03), (0x1f01, 0x0301, 0x1f05),
+    (0x1f01, 0x0342, 0x1f07), (0x1f01, 0x0345, 0x1f81), (0x1f02, 0x0345, 0x1f82),
+    (0x1f03, 0x0345, 0x1f83), (0x1f04, 0x0345, 0x1f84), (0x1f05, 0x0345, 0x1f85),
+    (0x1f06, 0x0345, 0x1f86), (0x1f07, 0x0345, 0x1f87), (0x1f08, 0x0300, 0x1f0a),
+    (0x1f08, 0x0301, 0x1f0c), (0x1f08, 0x0342, 0x1f0e), (0x1f08, 0x0345, 0x1f88),
+    (0x1f09, 0x0300, 0x1f0b), (0x1f09, 0x0301, 0x1f0d), (0x1f09, 0x0342, 0x1f0f),
+    (0x1f09, 0x0345, 0x1f89), (0x1f0a, 0x0345, 0x1f8a), (0x1f0b, 0x0345, 0x1f8b),
+    (0x1f0c, 0x0345, 0x1f8c), (0x1f0d, 0x0345, 0x1f8d), (0x1f0e, 0x0345, 0x1f8e),
+    (0x1f0f, 0x0345, 0x1f8f), (0x1f10, 0x0300, 0x1f12), (0x1f10, 0x0301, 0x1f14),
+    (0x1f11, 0x0300, 0x1f13), (0x1f11, 0x0301, 0x1f15), (0x1f18, 0x0300, 0x1f1a),
+    (0x1f18, 0x0301, 0x1f1c), (0x1f19, 0x0300, 0x1f1b), (0x1f19, 0x0301, 0x1f1d),
+    (0x1f20, 0x0300, 0x1f22), (0x1f20, 0x0301, 0x1f24), (0x1f20, 0x0342, 0x1f26),
+    (0x1f20, 0x0345, 0x1f90), (0x1f21, 0x0300, 0x1f23), (0x1f21, 0x0301, 0x1f25),
+    (0x1f21, 0x0342, 0x1f27), (0x1f21, 0x0345, 0x1f91), (0x1f22, 0x0345, 0x1f92),
+    (0x1f23, 0x0345, 0x1f93), (0x1f24, 0x0345, 0x1f94), (0x1f25, 0x0345, 0x1f95),
+    (0x1f26, 0x0345, 0x1f96), (0x1f27, 0x0345, 0x1f97), (0x1f28, 0x0300, 0x1f2a),
+    (0x1f28, 0x0301, 0x1f2c), (0x1f28, 0x0342, 0x1f2e), (0x1f28, 0x0345, 0x1f98),
+    (0x1f29, 0x0300, 0x1f2b), (0x1f29, 0x0301, 0x1f2d), (0x1f29, 0x0342, 0x1f2f),
+    (0x1f29, 0x0345, 0x1f99), (0x1f2a, 0x0345, 0x1f9a), (0x1f2b, 0x0345, 0x1f9b),
+    (0x1f2c, 0x0345, 0x1f9c), (0x1f2d, 0x0345, 0x1f9d), (0x1f2e, 0x0345, 0x1f9e),
+    (0x1f2f, 0x0345, 0x1f9f), (0x1f30, 0x0300, 0x1f32), (0x1f30, 0x0301, 0x1f34),
+    (0x1f30, 0x0342, 0x1f36), (0x1f31, 0x0300, 0x1f33), (0x1f31, 0x0301, 0x1f35),
+    (0x1f31, 0x0342, 0x1f37), (0x1f38, 0x0300, 0x1f3a), (0x1f38, 0x0301, 0x1f3c),
+    (0x1f38, 0x0342, 0x1f3e), (0x1f39, 0x0300, 0x1f3b), (0x1f39, 0x0301, 0x1f3d),
+    (0x1f39, 0x0342, 0x1f3f), (0x1f40, 0x0300, 0x1f42), (0x1f40, 0x0301, 0x1f44),
+    (0x1f41, 0x0300, 0x1f43), (0x1f41, 0x0301, 0x1f45), (0x1f48, 0x0300, 0x1f4a),
+    (0x1f48, 0x0301, 0x1f4c), (0x1f49, 0x0300, 0x1f4b), (0x1f49, 0x0301, 0x1f4d),
+    (0x1f50, 0x0300, 0x1f52), (0x1f50, 0x0301, 0x1f54), (0x1f50, 0x0342, 0x1f56),
+    (0x1f51, 0x0300, 0x1f53), (0x1f51, 0x0301, 0x1f55), (0x1f51, 0x0342, 0x1f57),
+    (0x1f59, 0x0300, 0x1f5b), (0x1f59, 0x0301, 0x1f5d), (0x1f59, 0x0342, 0x1f5f),
+    (0x1f60, 0x0300, 0x1f62), (0x1f60, 0x0301, 0x1f64), (0x1f60, 0x0342, 0x1f66),
+    (0x1f60, 0x0345, 0x1fa0), (0x1f61, 0x0300, 0x1f63), (0x1f61, 0x0301, 0x1f65),
+    (0x1f61, 0x0342, 0x1f67), (0x1f61, 0x0345, 0x1fa1), (0x1f62, 0x0345, 0x1fa2),
+    (0x1f63, 0x0345, 0x1fa3), (0x1f64, 0x0345, 0x1fa4), (0x1f65, 0x0345, 0x1fa5),
+    (0x1f66, 0x0345, 0x1fa6), (0x1f67, 0x0345, 0x1fa7), (0x1f68, 0x0300, 0x1f6a),
+    (0x1f68, 0x0301, 0x1f6c), (0x1f68, 0x0342, 0x1f6e), (0x1f68, 0x0345, 0x1fa8),
+    (0x1f69, 0x0300, 0x1f6b), (0x1f69, 0x0301, 0x1f6d), (0x1f69, 0x0342, 0x1f6f),
+    (0x1f69, 0x0345, 0x1fa9), (0x1f6a, 0x0345, 0x1faa), (0x1f6b, 0x0345, 0x1fab),
+    (0x1f6c, 0x0345, 0x1fac), (0x1f6d, 0x0345, 0x1fad), (0x1f6e, 0x0345, 0x1fae),
+    (0x1f6f, 0x0345, 0x1faf), (0x1f70, 0x0345, 0x1fb2), (0x1f74, 0x0345, 0x1fc2),
+    (0x1f7c, 0x0345, 0x1ff2), (0x1fb6, 0x0345, 0x1fb7), (0x1fbf, 0x0300, 0x1fcd),
+    (0x1fbf, 0x0301, 0x1fce), (0x1fbf, 0x0342, 0x1fcf), (0x1fc6, 0x0345, 0x1fc7),
+    (0x1ff6, 0x0345, 0x1ff7), (0x1ffe, 0x0300, 0x1fdd), (0x1ffe, 0x0301, 0x1fde),
+    (0x1ffe, 0x0342, 0x1fdf), (0x2190, 0x0338, 0x219a), (0x2192, 0x0338, 0x219b),
+    (0x2194, 0x0338, 0x21ae), (0x21d0, 0x0338, 0x21cd), (0x21d2, 0x0338, 0x21cf),
+    (0x21d4, 0x0338, 0x21ce), (0x2203, 0x0338, 0x2204), (0x2208, 0x0338, 0x2209),
+    (0x220b, 0x0338, 0x220c), (0x2223, 0x0338, 0x2224), (0x2225, 0x0338, 0x2226),
+    (0x223c, 0x0338, 0x2241), (0x2243, 0x0338, 0x2244), (0x2245, 0x0338, 0x2247),
+    (0x2248, 0x0338, 0x2249), (0x224d, 0x0338, 0x226d), (0x2261, 0x0338, 0x2262),
+    (0x2264, 0x0338, 0x2270), (0x2265, 0x0338, 0x2271), (0x2272, 0x0338, 0x2274),
+    (0x2273, 0x0338, 0x2275), (0x2276, 0x0338, 0x2278), (0x2277, 0x0338, 0x2279),
+    (0x227a, 0x0338, 0x2280), (0x227b, 0x0338, 0x2281), (0x227c, 0x0338, 0x22e0),
+    (0x227d, 0x0338, 0x22e1), (0x2282, 0x0338, 0x2284), (0x2283, 0x0338, 0x2285),
+    (0x2286, 0x0338, 0x2288), (0x2287, 0x0338, 0x2289), (0x2291, 0x0338, 0x22e2),
+    (0x2292, 0x0338, 0x22e3), (0x22a2, 0x0338, 0x22ac), (0x22a8, 0x0338, 0x22ad),
+    (0x22a9, 0x0338, 0x22ae), (0x22ab, 0x0338, 0x22af), (0x22b2, 0x0338, 0x22ea),
+    (0x22b3, 0x0338, 0x22eb), (0x22b4, 0x0338, 0x22ec), (0x22b5, 0x0338, 0x22ed),
+    (0x3046, 0x3099, 0x3094), (0x304b, 0x3099, 0x304c), (0x304d, 0x3099, 0x304e),
+    (0x304f, 0x3099, 0x3050), (0x3051, 0x3099, 0x3052), (0x3053, 0x3099, 0x3054),
+    (0x3055, 0x3099, 0x3056), (0x3057, 0x3099, 0x3058), (0x3059, 0x3099, 0x305a),
+    (0x305b, 0x3099, 0x305c), (0x305d, 0x3099, 0x305e), (0x305f, 0x3099, 0x3060),
+    (0x3061, 0x3099, 0x3062), (0x3064, 0x3099, 0x3065), (0x3066, 0x3099, 0x3067),
+    (0x3068, 0x3099, 0x3069), (0x306f, 0x3099, 0x3070), (0x306f, 0x309a, 0x3071),
+    (0x3072, 0x3099, 0x3073), (0x3072, 0x309a, 0x3074), (0x3075, 0x3099, 0x3076),
+    (0x3075, 0x309a, 0x3077), (0x3078, 0x3099, 0x3079), (0x3078, 0x309a, 0x307a),
+    (0x307b, 0x3099, 0x307c), (0x307b, 0x309a, 0x307d), (0x309d, 0x3099, 0x309e),
+    (0x30a6, 0x3099, 0x30f4), (0x30ab, 0x3099, 0x30ac), (0x30ad, 0x3099, 0x30ae),
+    (0x30af, 0x3099, 0x30b0), (0x30b1, 0x3099, 0x30b2), (0x30b3, 0x3099, 0x30b4),
+    (0x30b5, 0x3099, 0x30b6), (0x30b7, 0x3099, 0x30b8), (0x30b9, 0x3099, 0x30ba),
+    (0x30bb, 0x3099, 0x30bc), (0x30bd, 0x3099, 0x30be), (0x30bf, 0x3099, 0x30c0),
+    (0x30c1, 0x3099, 0x30c2), (0x30c4, 0x3099, 0x30c5), (0x30c6, 0x3099, 0x30c7),
+    (0x30c8, 0x3099, 0x30c9), (0x30cf, 0x3099, 0x30d0), (0x30cf, 0x309a, 0x30d1),
+    (0x30d2, 0x3099, 0x30d3), (0x30d2, 0x309a, 0x30d4), (0x30d5, 0x3099, 0x30d6),
+    (0x30d5, 0x309a, 0x30d7), (0x30d8, 0x3099, 0x30d9), (0x30d8, 0x309a, 0x30da),
+    (0x30db, 0x3099, 0x30dc), (0x30db, 0x309a, 0x30dd), (0x30ef, 0x3099, 0x30f7),
+    (0x30f0, 0x3099, 0x30f8), (0x30f1, 0x3099, 0x30f9), (0x30f2, 0x3099, 0x30fa),
+    (0x30fd, 0x3099, 0x30fe),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_char_width() {
+        assert_eq!(char_width('a'), 1);
+        assert_eq!(char_width('\u{3042}'), 2);
+        assert_eq!(char_width('\u{ff21}'), 2);
+        assert_eq!(char_width('\u{301}'), 0);
+        assert_eq!(char_width('\u{3099}'), 0);
+        assert_eq!(char_width('\u{5b4}'), 0);
+        assert_eq!(char_width('\u{64e}'), 0);
+        assert_eq!(char_width('\u{941}'), 0);
+        assert_eq!(char_width('\u{e34}'), 0);
+        assert_eq!(char_width('\u{200d}'), 0);
+        assert_eq!(char_width('\u{20dd}'), 0);
+        // Spacing marks and the letters around the ranges take a cell.
+        assert_eq!(char_width('\u{93e}'), 1);
+        assert_eq!(char_width('\u{5d0}'), 1);
+        assert_eq!(char_width('\u{2ff}'), 1);
+        assert_eq!(char_width('\u{370}'), 1);
+        assert!(is_fullwidth('\u{4e00}'));
+        assert!(!is_fullwidth('e'));
+    }
+
+    #[test]
+    fn test_compose() {
+        assert_eq!(compose('\u{304b}', '\u{3099}'), Some('\u{304c}'));
+        assert_eq!(compose('\u{30cf}', '\u{309a}'), Some('\u{30d1}'));
+        assert_eq!(compose('e', '\u{301}'), Some('\u{e9}'));
+        assert_eq!(compose('\u{1eb9}', '\u{302}'), Some('\u{1ec7}'));
+        assert_eq!(compose('x', '\u{301}'), None);
+        assert_eq!(compose('\u{3042}', '\u{3099}'), None);
+    }
+
+    #[test]
+    fn test_utf8() {
+        assert_eq!(utf8_to_utf32(b"a"), Some(('a', 1)));
+        assert_eq!(utf8_to_utf32("\u{3042}x".as_bytes()), Some(('\u{3042}', 3)));
+        assert_eq!(utf8_to_utf32(&"\u{3042}".as_bytes()[..2]), None);
+        assert_eq!(utf8_to_utf32(b""), None);
+        assert_eq!(utf8_to_utf32(b"\x80"), None);
+        assert_eq!(utf8_to_utf32(b"\xc0\xaf"), None);
+        assert_eq!(utf8_to_utf32(b"\xed\xa0\x80"), None);
+        assert_eq!(utf32_to_utf8('\u{e9}'), "\u{e9}");
+    }
+
+    #[test]
+    fn test_cp437_and_ascii() {
+        assert_eq!(cp437_to_utf32(b'A'), 'A');
+        assert_eq!(cp437_to_utf32(0xdb), '\u{2588}');
+        assert_eq!(utf32_to_cp437('\u{2591}'), 0xb0);
+        assert_eq!(cp437_to_string(&[0xc9, 0xcd, 0xbb]), "\u{2554}\u{2550}\u{2557}");
+        assert_eq!(string_to_cp437("\u{2554}A"), vec![0xc9, b'A']);
+
+        assert_eq!(cp437_to_utf32(b'\n'), '\u{25d9}');
+        let art = b"\x1b[31m\xdb\r\n\t\x01";
+        assert_eq!(cp437_to_string(art), "\x1b[31m\u{2588}\r\n\t\u{263a}");
+        assert_eq!(string_to_cp437(&cp437_to_string(art)), art.to_vec());
+
+        assert_eq!(utf32_to_ascii('x'), 'x');
+        assert!(string_to_ascii("\u{2554}\u{2550}\u{2557}").is_ascii());
+    }
+}
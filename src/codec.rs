@@ -0,0 +1,552 @@
+use std::ffi::{CStr, CString};
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::slice;
+use errno::errno;
+use libc::{self, c_void, size_t, ssize_t};
+
+use caca::*;
+use file::CacaFile;
+use ::{Canvas, CanvasView, CacaError, CacaResult, described_list};
+
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum ExportFormat {
+    Caca,
+    Ansi,
+    Utf8,
+    Utf8Cr,
+    Html,
+    Html3,
+    BbFr,
+    Irc,
+    Ps,
+    Svg,
+    Tga,
+    Troff,
+}
+
+impl ExportFormat {
+    fn from_cstr(cs: &CStr) -> Option<ExportFormat> {
+        let string = cs.to_str().unwrap();
+        match string {
+            "caca"   => Some(ExportFormat::Caca),
+            "ansi"   => Some(ExportFormat::Ansi),
+            "utf8"   => Some(ExportFormat::Utf8),
+            "utf8cr" => Some(ExportFormat::Utf8Cr),
+            "html"   => Some(ExportFormat::Html),
+            "html3"  => Some(ExportFormat::Html3),
+            "bbfr"   => Some(ExportFormat::BbFr),
+            "irc"    => Some(ExportFormat::Irc),
+            "ps"     => Some(ExportFormat::Ps),
+            "svg"    => Some(ExportFormat::Svg),
+            "tga"    => Some(ExportFormat::Tga),
+            "troff"  => Some(ExportFormat::Troff),
+            _        => None,
+        }
+    }
+
+    pub fn from_extension(ext: &str) -> Option<ExportFormat> {
+        match &*ext.to_lowercase() {
+            "caca"         => Some(ExportFormat::Caca),
+            "ans"          => Some(ExportFormat::Ansi),
+            "txt"          => Some(ExportFormat::Utf8),
+            "html" | "htm" => Some(ExportFormat::Html),
+            "svg"          => Some(ExportFormat::Svg),
+            "tga"          => Some(ExportFormat::Tga),
+            "ps"           => Some(ExportFormat::Ps),
+            _              => None,
+        }
+    }
+
+    fn to_cstring(&self) -> CString {
+        let format_name = match *self {
+            ExportFormat::Caca   => "caca",
+            ExportFormat::Ansi   => "ansi",
+            ExportFormat::Utf8   => "utf8",
+            ExportFormat::Utf8Cr => "utf8cr",
+            ExportFormat::Html   => "html",
+            ExportFormat::Html3  => "html3",
+            ExportFormat::BbFr   => "bbfr",
+            ExportFormat::Irc    => "irc",
+            ExportFormat::Ps     => "ps",
+            ExportFormat::Svg    => "svg",
+            ExportFormat::Tga    => "tga",
+            ExportFormat::Troff  => "troff",
+        };
+        CString::new(format_name).unwrap()
+    }
+}
+
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum ImportFormat {
+    Auto,
+    Caca,
+    Ansi,
+    Text,
+    Utf8,
+    Bin,
+}
+
+impl ImportFormat {
+    fn from_cstr(cs: &CStr) -> Option<ImportFormat> {
+        let string = cs.to_str().unwrap();
+        match string {
+            ""     => Some(ImportFormat::Auto),
+            "caca" => Some(ImportFormat::Caca),
+            "ansi" => Some(ImportFormat::Ansi),
+            "text" => Some(ImportFormat::Text),
+            "utf8" => Some(ImportFormat::Utf8),
+            "bin"  => Some(ImportFormat::Bin),
+            _      => None,
+        }
+    }
+
+    /// Falls back to `ImportFormat::Auto` for extensions without a dedicated
+    /// importer.
+    pub fn from_extension(ext: &str) -> ImportFormat {
+        match &*ext.to_lowercase() {
+            "caca" => ImportFormat::Caca,
+            "ans"  => ImportFormat::Ansi,
+            "txt"  => ImportFormat::Utf8,
+            _      => ImportFormat::Auto,
+        }
+    }
+
+    fn to_cstring(&self) -> CString {
+        let format_name = match *self {
+            ImportFormat::Auto => "",
+            ImportFormat::Caca => "caca",
+            ImportFormat::Ansi => "ansi",
+            ImportFormat::Text => "text",
+            ImportFormat::Utf8 => "utf8",
+            ImportFormat::Bin  => "bin",
+        };
+        CString::new(format_name).unwrap()
+    }
+}
+
+/// The export formats supported by the linked libcaca, with their
+/// descriptions. Formats unknown to this crate are left out.
+pub fn export_formats() -> Vec<(ExportFormat, String)> {
+    let list = unsafe { caca_get_export_list() };
+    described_list(list).into_iter()
+        .filter_map(|(name, description)| {
+            ExportFormat::from_cstr(name).map(|format| (format, description.to_string_lossy().into_owned()))
+        })
+        .collect()
+}
+
+/// The import formats supported by the linked libcaca, with their
+/// descriptions. Formats unknown to this crate are left out.
+pub fn import_formats() -> Vec<(ImportFormat, String)> {
+    let list = unsafe { caca_get_import_list() };
+    described_list(list).into_iter()
+        .filter_map(|(name, description)| {
+            ImportFormat::from_cstr(name).map(|format| (format, description.to_string_lossy().into_owned()))
+        })
+        .collect()
+}
+
+impl Canvas {
+    pub fn load_from<R: Read>(reader: &mut R, format: ImportFormat) -> Result<Self, CacaError> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let mut canvas = Canvas::new(0, 0)?;
+        if canvas.import(&data, format)? == 0 {
+            return Err(CacaError::Io(io::Error::new(io::ErrorKind::UnexpectedEof,
+                                                    "not enough data to import a canvas")));
+        }
+        Ok(canvas)
+    }
+
+    /// Loads a canvas from a file, picking the import format from the file
+    /// extension and autodetecting it if the extension is not recognized.
+    /// Compressed files are decompressed transparently.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, CacaError> {
+        let format = file_extension(path.as_ref())
+            .map_or(ImportFormat::Auto, ImportFormat::from_extension);
+        let mut file = CacaFile::open(path)?;
+        Canvas::load_from(&mut file, format)
+    }
+}
+
+impl CanvasView {
+    pub fn export(&self, format: ExportFormat) -> Result<Vec<u8>, CacaError> {
+        let format_cstring = format.to_cstring();
+        let mut len: size_t = 0;
+        let buf = unsafe { caca_export_canvas_to_memory(self.raw(), format_cstring.as_ptr(), &mut len) };
+        take_export_buffer(buf, len)
+    }
+
+    /// Replaces the contents of the canvas with the imported data, resizing
+    /// it as needed. Returns the number of bytes read, which is 0 if there
+    /// was not enough data to import a full canvas.
+    pub fn import(&mut self, data: &[u8], format: ImportFormat) -> Result<usize, CacaError> {
+        let format_cstring = format.to_cstring();
+        let result = unsafe { caca_import_canvas_from_memory(self.raw(),
+                                                             data.as_ptr() as *const c_void,
+                                                             data.len() as size_t,
+                                                             format_cstring.as_ptr()) };
+        import_result(result)
+    }
+
+    pub fn export_area(&self, x: i32, y: i32, w: i32, h: i32,
+                       format: ExportFormat) -> Result<Vec<u8>, CacaError> {
+        let format_cstring = format.to_cstring();
+        let mut len: size_t = 0;
+        let buf = unsafe { caca_export_area_to_memory(self.raw(), x, y, w, h,
+                                                      format_cstring.as_ptr(), &mut len) };
+        take_export_buffer(buf, len)
+    }
+
+    /// Imports the data into the area of the canvas starting at the given
+    /// coordinates, leaving the rest of the canvas and its size untouched.
+    pub fn import_area(&mut self, x: i32, y: i32, data: &[u8],
+                       format: ImportFormat) -> Result<usize, CacaError> {
+        let format_cstring = format.to_cstring();
+        let result = unsafe { caca_import_area_from_memory(self.raw(), x, y,
+                                                           data.as_ptr() as *const c_void,
+                                                           data.len() as size_t,
+                                                           format_cstring.as_ptr()) };
+        import_result(result)
+    }
+
+    pub fn save_to<W: Write>(&self, writer: &mut W, format: ExportFormat) -> CacaResult {
+        let data = self.export(format)?;
+        writer.write_all(&data)?;
+        Ok(())
+    }
+
+    /// Saves the canvas to a file, picking the export format from the file
+    /// extension. Files ending in `.gz` are compressed.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> CacaResult {
+        let format = file_extension(path.as_ref())
+            .and_then(ExportFormat::from_extension);
+        match format {
+            Some(format_) => {
+                if is_gzip_path(path.as_ref()) {
+                    let mut file = CacaFile::create(path)?;
+                    self.save_to(&mut file, format_)?;
+                    file.close()?;
+                    Ok(())
+                } else {
+                    let mut file = File::create(path)?;
+                    self.save_to(&mut file, format_)
+                }
+            },
+            None => Err(CacaError::UnsupportedFormat),
+        }
+    }
+
+    /// Exports every frame of the canvas in the native caca format, which
+    /// keeps the frame handles, followed by a table of the frame names.
+    /// Importing the result at once with libcaca, as `import` does, reads the
+    /// frames and ignores the table. Readers expecting a stream of back to
+    /// back canvases, such as cacaplay, stop at the table, which does not
+    /// start with the caca magic and so cannot be mistaken for a canvas.
+    pub fn export_frames(&mut self) -> Result<Vec<u8>, CacaError> {
+        let mut data = self.export(ExportFormat::Caca)?;
+        let names = self.frame_names();
+
+        data.extend_from_slice(FRAME_NAMES_MAGIC);
+        push_u32(&mut data, names.len() as u32);
+        for name in names {
+            push_u32(&mut data, name.len() as u32);
+            data.extend_from_slice(name.as_bytes());
+        }
+        Ok(data)
+    }
+
+    /// Imports all frames written by `export_frames`, restoring their names
+    /// if present. Returns the number of bytes read, which is 0 if there was
+    /// not enough data for the frames. A missing or truncated name table is
+    /// skipped, leaving the frames with their default names, and is not
+    /// counted as read.
+    pub fn import_frames(&mut self, data: &[u8]) -> Result<usize, CacaError> {
+        let read = self.import(data, ImportFormat::Caca)?;
+        if read == 0 {
+            return Ok(0);
+        }
+        let (names, table_len) = match read_frame_names(&data[read..]) {
+            Some(table) => table,
+            None        => return Ok(read),
+        };
+
+        for (i, name) in names.iter().enumerate().take(self.frame_count() as usize) {
+            self.set_frame(i as i32)?;
+            self.set_frame_name(name)?;
+        }
+        self.set_frame(0)?;
+        Ok(read + table_len)
+    }
+}
+
+// Parses the frame name table at the start of the data, returning the names
+// and the length of the table.
+fn read_frame_names(data: &[u8]) -> Option<(Vec<String>, usize)> {
+    if !data.starts_with(FRAME_NAMES_MAGIC) {
+        return None;
+    }
+    let mut pos = FRAME_NAMES_MAGIC.len();
+    let count = read_u32(data, &mut pos)?;
+    let mut names = Vec::new();
+    for _ in 0..count {
+        let len = read_u32(data, &mut pos)? as usize;
+        if data.len() - pos < len {
+            return None;
+        }
+        names.push(String::from_utf8_lossy(&data[pos..pos + len]).into_owned());
+        pos += len;
+    }
+    Some((names, pos))
+}
+
+// Must not start with the caca magic, 0xCA 0xCA.
+const FRAME_NAMES_MAGIC: &[u8] = b"CRFN";
+
+fn push_u32(data: &mut Vec<u8>, value: u32) {
+    data.extend_from_slice(&[(value >> 24) as u8, (value >> 16) as u8,
+                             (value >> 8) as u8, value as u8]);
+}
+
+fn read_u32(data: &[u8], pos: &mut usize) -> Option<u32> {
+    if data.len() - *pos < 4 {
+        return None;
+    }
+    let bytes = &data[*pos..*pos + 4];
+    *pos += 4;
+    Some((bytes[0] as u32) << 24 | (bytes[1] as u32) << 16 |
+         (bytes[2] as u32) << 8 | bytes[3] as u32)
+}
+
+fn is_gzip_path(path: &Path) -> bool {
+    path.extension().and_then(|ext| ext.to_str()) == Some("gz")
+}
+
+// The extension naming the canvas format, looking past a trailing ".gz".
+fn file_extension(path: &Path) -> Option<&str> {
+    let path = if is_gzip_path(path) {
+        match path.file_stem() {
+            Some(stem) => Path::new(stem),
+            None       => return None,
+        }
+    } else {
+        path
+    };
+    path.extension().and_then(|ext| ext.to_str())
+}
+
+fn import_result(result: ssize_t) -> Result<usize, CacaError> {
+    if result >= 0 {
+        Ok(result as usize)
+    } else {
+        let errno = errno().0;
+        match errno {
+            libc::EINVAL => Err(CacaError::UnsupportedFormat),
+            libc::ENOMEM => Err(CacaError::NotEnoughMemory),
+            _            => Err(CacaError::Unknown(errno)),
+        }
+    }
+}
+
+// The exported buffer is allocated by libcaca with malloc(), so it has to be
+// copied out and released with free().
+fn take_export_buffer(buf: *mut c_void, len: size_t) -> Result<Vec<u8>, CacaError> {
+    if buf.is_null() {
+        let errno = errno().0;
+        match errno {
+            libc::EINVAL => Err(CacaError::UnsupportedFormat),
+            libc::ENOMEM => Err(CacaError::NotEnoughMemory),
+            _            => Err(CacaError::Unknown(errno)),
+        }
+    } else {
+        let data = unsafe { slice::from_raw_parts(buf as *const u8, len).to_vec() };
+        unsafe { libc::free(buf) };
+        Ok(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+    use Rect;
+    use super::*;
+
+    #[test]
+    fn test_format_lists() {
+        let export = export_formats();
+        assert!(export.iter().any(|(format, _)| *format == ExportFormat::Caca));
+        assert!(export.iter().all(|(_, description)| !description.is_empty()));
+
+        let import = import_formats();
+        assert!(import.iter().any(|(format, _)| *format == ImportFormat::Auto));
+    }
+
+    #[test]
+    fn test_export() {
+        let mut canvas = Canvas::new(10, 2).unwrap();
+        canvas.put_str(0, 0, "caca");
+
+        let utf8 = canvas.export(ExportFormat::Utf8);
+        assert!(utf8.is_ok(), "{:?}", utf8.err());
+        let utf8 = String::from_utf8(utf8.unwrap()).unwrap();
+        assert!(utf8.contains("caca"));
+
+        let caca = canvas.export(ExportFormat::Caca).unwrap();
+        assert!(caca.starts_with(b"\xca\xca"));
+    }
+
+    #[test]
+    fn test_import() {
+        let mut source = Canvas::new(10, 2).unwrap();
+        source.put_str(0, 1, "caca");
+        let data = source.export(ExportFormat::Caca).unwrap();
+
+        let mut canvas = Canvas::new(0, 0).unwrap();
+        let result = canvas.import(&data, ImportFormat::Auto);
+        assert!(result.is_ok(), "{:?}", result.err());
+        assert_eq!(result.unwrap(), data.len());
+        assert_eq!(canvas.width(), 10);
+        assert_eq!(canvas.height(), 2);
+
+        let result = canvas.import(b"plain text", ImportFormat::Text);
+        assert!(result.is_ok(), "{:?}", result.err());
+        assert_eq!(canvas.width(), 10);
+        assert_eq!(canvas.height(), 1);
+    }
+
+    #[test]
+    fn test_area() {
+        let mut source = Canvas::new(20, 10).unwrap();
+        source.put_str(5, 5, "area");
+        let data = source.export_area(4, 4, 6, 3, ExportFormat::Caca);
+        assert!(data.is_ok(), "{:?}", data.err());
+        let data = data.unwrap();
+
+        let mut canvas = Canvas::new(30, 30).unwrap();
+        let result = canvas.import_area(10, 10, &data, ImportFormat::Caca);
+        assert!(result.is_ok(), "{:?}", result.err());
+        assert_eq!(canvas.width(), 30);
+        assert_eq!(canvas.height(), 30);
+        let text = String::from_utf8(canvas.export_area(11, 11, 4, 1, ExportFormat::Utf8).unwrap()).unwrap();
+        assert!(text.contains("area"));
+    }
+
+    #[test]
+    fn test_load_save() {
+        let mut source = Canvas::new(10, 2).unwrap();
+        source.put_str(0, 0, "saved");
+
+        let mut data = Vec::new();
+        let result = source.save_to(&mut data, ExportFormat::Caca);
+        assert!(result.is_ok(), "{:?}", result.err());
+
+        let canvas = Canvas::load_from(&mut &data[..], ImportFormat::Auto);
+        assert!(canvas.is_ok(), "{:?}", canvas.err());
+        let canvas = canvas.unwrap();
+        assert_eq!(canvas.width(), 10);
+        assert_eq!(canvas.height(), 2);
+
+        let canvas = Canvas::load_from(&mut &data[..4], ImportFormat::Caca);
+        assert!(canvas.is_err());
+
+        assert_eq!(ExportFormat::from_extension("HTML"), Some(ExportFormat::Html));
+        assert_eq!(ExportFormat::from_extension("png"), None);
+        assert_eq!(ImportFormat::from_extension("ans"), ImportFormat::Ansi);
+        assert_eq!(ImportFormat::from_extension("png"), ImportFormat::Auto);
+    }
+
+    #[test]
+    fn test_open_save_text() {
+        let path = env::temp_dir().join("caca-rs-test-codec.txt");
+
+        let mut source = Canvas::new(10, 2).unwrap();
+        source.set_color_ansi(&::AnsiColor::Red, &::AnsiColor::Black);
+        source.put_str(0, 0, "\u{e9}t\u{e9}");
+        let result = source.save(&path);
+        assert!(result.is_ok(), "{:?}", result.err());
+
+        // Text files are written with color escapes, which must be parsed
+        // rather than read back as characters.
+        let canvas = Canvas::open(&path);
+        assert!(canvas.is_ok(), "{:?}", canvas.err());
+        let canvas = canvas.unwrap();
+        assert_eq!(canvas.width(), 10);
+        assert_eq!(canvas.get_char(0, 0), '\u{e9}');
+        assert_eq!(canvas.get_char(1, 0), 't');
+        assert_eq!(canvas.get_char(2, 0), '\u{e9}');
+        assert_eq!(canvas.get_attr(0, 0).ansi_fg(), Some(::AnsiColor::Red));
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_frames() {
+        let mut source = Canvas::new(4, 4).unwrap();
+        source.set_frame_name("idle").unwrap();
+        source.create_frame(1).unwrap();
+        source.set_frame(1).unwrap();
+        source.set_frame_name("walk").unwrap();
+        source.set_handle(2, 3);
+        source.put_str(0, 0, "walk");
+        source.clear_dirty_rect_list();
+        source.put_char(1, 1, 'x');
+        let dirty: Vec<Rect> = source.dirty_rects().collect();
+
+        let data = source.export_frames();
+        assert!(data.is_ok(), "{:?}", data.err());
+        let data = data.unwrap();
+        let read = Canvas::new(0, 0).unwrap().import(&data, ImportFormat::Caca).unwrap();
+        assert!(read < data.len());
+        assert!(!data[read..].starts_with(b"\xca\xca"));
+        assert_eq!(source.get_frame_name(), "walk");
+        assert_eq!(source.dirty_rects().collect::<Vec<Rect>>(), dirty);
+        assert!(source.enable_dirty_rect().is_err());
+
+        let mut canvas = Canvas::new(0, 0).unwrap();
+        let result = canvas.import_frames(&data);
+        assert!(result.is_ok(), "{:?}", result.err());
+        assert_eq!(result.unwrap(), data.len());
+        assert_eq!(canvas.frame_count(), 2);
+        assert_eq!(canvas.frame_names(), vec!["idle", "walk"]);
+
+        canvas.set_frame(1).unwrap();
+        assert_eq!(canvas.handle_x(), 2);
+        assert_eq!(canvas.handle_y(), 3);
+
+        // The frames are still imported when the name table is cut short.
+        let mut canvas = Canvas::new(0, 0).unwrap();
+        assert_eq!(canvas.import_frames(&data[..data.len() - 1]).unwrap(), read);
+        assert_eq!(canvas.frame_count(), 2);
+        assert!(canvas.frame_names() != vec!["idle", "walk"]);
+        assert_eq!(canvas.import_frames(&data[..4]).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_open_save_gzip() {
+        let path = env::temp_dir().join("caca-rs-test-codec.caca.gz");
+        assert_eq!(file_extension(&path), Some("caca"));
+
+        let mut source = Canvas::new(8, 3).unwrap();
+        source.put_str(0, 2, "gzipped");
+        let result = source.save(&path);
+        assert!(result.is_ok(), "{:?}", result.err());
+
+        let canvas = Canvas::open(&path);
+        assert!(canvas.is_ok(), "{:?}", canvas.err());
+        let canvas = canvas.unwrap();
+        assert_eq!(canvas.width(), 8);
+        assert_eq!(canvas.height(), 3);
+
+        fs::remove_file(&path).unwrap();
+
+        let canvas = Canvas::open(&path);
+        match canvas {
+            Err(CacaError::Io(ref err)) if err.kind() == io::ErrorKind::NotFound => (),
+            _ => panic!("{:?}", canvas.err()),
+        }
+    }
+}
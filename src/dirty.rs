@@ -0,0 +1,172 @@
+use std::iter::FusedIterator;
+use errno::errno;
+use libc::{self, c_int};
+
+use caca::*;
+use ::{CanvasView, CacaError, CacaResult};
+
+/// A rectangular area of a canvas, in character cells.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub w: i32,
+    pub h: i32,
+}
+
+impl Rect {
+    pub fn new(x: i32, y: i32, w: i32, h: i32) -> Rect {
+        Rect { x: x, y: y, w: w, h: h }
+    }
+
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.x && x < self.x + self.w && y >= self.y && y < self.y + self.h
+    }
+}
+
+/// Iterator over the dirty rectangles of a canvas, returned by
+/// `CanvasView::dirty_rects()`.
+pub struct DirtyRects<'b> {
+    canvas: &'b CanvasView,
+    index: i32,
+    count: i32,
+}
+
+impl<'b> Iterator for DirtyRects<'b> {
+    type Item = Rect;
+
+    fn next(&mut self) -> Option<Rect> {
+        if self.index >= self.count {
+            return None;
+        }
+        let rect = self.canvas.dirty_rect(self.index);
+        self.index += 1;
+        rect
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.count - self.index) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'b> ExactSizeIterator for DirtyRects<'b> {}
+
+impl<'b> FusedIterator for DirtyRects<'b> {}
+
+impl CanvasView {
+    /// Stops tracking the areas changed by drawing functions. Calls nest, so
+    /// tracking resumes after as many calls to `enable_dirty_rect`.
+    pub fn disable_dirty_rect(&mut self) {
+        unsafe { caca_disable_dirty_rect(self.raw()) };
+    }
+
+    pub fn enable_dirty_rect(&mut self) -> CacaResult {
+        let result = unsafe { caca_enable_dirty_rect(self.raw()) };
+        if result == 0 {
+            Ok(())
+        } else {
+            let errno = errno().0;
+            match errno {
+                libc::EINVAL => Err(CacaError::DirtyRectsNotDisabled),
+                _            => Err(CacaError::Unknown(errno)),
+            }
+        }
+    }
+
+    pub fn dirty_rect_count(&self) -> i32 {
+        unsafe { caca_get_dirty_rect_count(self.raw()) }
+    }
+
+    /// The dirty rectangle at the given index, clipped to the canvas.
+    pub fn dirty_rect(&self, index: i32) -> Option<Rect> {
+        let (mut x, mut y, mut w, mut h) = (0, 0, 0, 0);
+        let result = unsafe { caca_get_dirty_rect(self.raw(), index,
+                                                  &mut x, &mut y, &mut w, &mut h) };
+        if result == 0 {
+            Some(Rect::new(x, y, w, h))
+        } else {
+            None
+        }
+    }
+
+    /// The areas changed since the dirty rectangle list was last cleared.
+    /// libcaca merges rectangles when there are too many, so they may cover
+    /// unchanged cells as well.
+    pub fn dirty_rects<'b>(&'b self) -> DirtyRects<'b> {
+        DirtyRects {
+            canvas: self,
+            index: 0,
+            count: self.dirty_rect_count(),
+        }
+    }
+
+    /// Marks an area as dirty, for changes made without the drawing
+    /// functions.
+    pub fn add_dirty_rect(&mut self, rect: Rect) -> CacaResult {
+        rect_result(unsafe { caca_add_dirty_rect(self.raw(), rect.x, rect.y, rect.w, rect.h) })
+    }
+
+    /// Marks an area as clean, splitting the dirty rectangles overlapping it.
+    pub fn remove_dirty_rect(&mut self, rect: Rect) -> CacaResult {
+        rect_result(unsafe { caca_remove_dirty_rect(self.raw(), rect.x, rect.y, rect.w, rect.h) })
+    }
+
+    pub fn clear_dirty_rect_list(&mut self) {
+        unsafe { caca_clear_dirty_rect_list(self.raw()) };
+    }
+}
+
+fn rect_result(result: c_int) -> CacaResult {
+    if result == 0 {
+        Ok(())
+    } else {
+        let errno = errno().0;
+        match errno {
+            libc::EINVAL => Err(CacaError::InvalidRect),
+            _            => Err(CacaError::Unknown(errno)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use Canvas;
+    use super::*;
+
+    #[test]
+    fn test_dirty_rects() {
+        let mut canvas = Canvas::new(10, 10).unwrap();
+        canvas.clear_dirty_rect_list();
+        assert_eq!(canvas.dirty_rect_count(), 0);
+        assert_eq!(canvas.dirty_rects().count(), 0);
+        assert_eq!(canvas.dirty_rect(0), None);
+
+        canvas.put_char(2, 3, 'x');
+        assert!(canvas.dirty_rect_count() > 0);
+        assert!(canvas.dirty_rects().any(|rect| rect.contains(2, 3)));
+        assert!(canvas.dirty_rects().all(|rect| !rect.contains(8, 8)));
+
+        canvas.clear_dirty_rect_list();
+        let result = canvas.add_dirty_rect(Rect::new(5, 5, 2, 2));
+        assert!(result.is_ok(), "{:?}", result.err());
+        assert!(canvas.dirty_rects().any(|rect| rect.contains(6, 6)));
+        assert!(canvas.add_dirty_rect(Rect::new(0, 0, 0, 1)).is_err());
+    }
+
+    #[test]
+    fn test_disable_dirty_rect() {
+        let mut canvas = Canvas::new(10, 10).unwrap();
+        assert!(canvas.enable_dirty_rect().is_err());
+
+        canvas.clear_dirty_rect_list();
+        canvas.disable_dirty_rect();
+        canvas.put_char(2, 3, 'x');
+        assert_eq!(canvas.dirty_rect_count(), 0);
+
+        let result = canvas.enable_dirty_rect();
+        assert!(result.is_ok(), "{:?}", result.err());
+        canvas.put_char(2, 3, 'y');
+        assert!(canvas.dirty_rect_count() > 0);
+    }
+}
@@ -3,7 +3,7 @@ use errno::errno;
 use libc::{self, c_void};
 
 use caca::*;
-use ::{Canvas, Color, CacaError, CacaResult};
+use ::{CanvasView, Color, CacaError, CacaResult};
 
 #[derive(Eq, PartialEq, Debug, Clone)]
 pub enum DitherAntialias {
@@ -381,10 +381,10 @@ impl Dither {
     }
 }
 
-impl<'a> Canvas<'a> {
+impl CanvasView {
     pub fn dither_bitmap<T: Into<Vec<u8>>>(&mut self, x: i32, y: i32, w: i32, h: i32, dither: &Dither, image: T) {
         let image_buffer = image.into();
-        unsafe { caca_dither_bitmap(self.canvas,
+        unsafe { caca_dither_bitmap(self.raw(),
                                     x, y, w, h,
                                     dither.as_ptr(),
                                     image_buffer.as_ptr() as *const c_void) };
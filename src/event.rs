@@ -77,7 +77,7 @@ const NIL_RAW_EVENT: CacaEventRaw = CacaEventRaw { type_: 0, data: [0, 0, 0, 0,
                                                                     0, 0, 0, 0,
                                                                     0, 0, 0, 0] };
 
-impl Display {
+impl<'c> Display<'c> {
     pub fn poll_event(&self, mask: u32) -> Option<Event> {
         let mut ev = NIL_RAW_EVENT;
         unsafe { caca_get_event(self.display, mask as c_int, &mut ev, -1)};
@@ -0,0 +1,326 @@
+use std::env;
+use std::ffi::CString;
+use std::fs::{self, DirBuilder, OpenOptions};
+use std::io::{self, Read, Write};
+#[cfg(unix)] use std::os::unix::fs::DirBuilderExt;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use errno::errno;
+use libc::{self, c_int};
+
+use caca::*;
+use file::CacaFile;
+use ::{CanvasView, CacaError, CacaResult};
+
+// The German characters every FIGlet font defines after printable ASCII and
+// before any code tagged characters.
+const REQUIRED_CHARS: [u32; 7] = [196, 214, 220, 228, 246, 252, 223];
+
+static NEXT_FIGFONT_DIR: AtomicUsize = AtomicUsize::new(0);
+
+// How many temporary directory names to try before giving up, in case other
+// users created directories with the same names.
+const FIGFONT_DIR_ATTEMPTS: usize = 100;
+
+/// A FIGlet (.flf) or TOIlet (.tlf) font held in memory, so it can be
+/// embedded in the binary or loaded from any location.
+#[derive(Clone, Debug)]
+pub struct FigFont {
+    data: Vec<u8>,
+    height: i32,
+    baseline: i32,
+    code_points: Vec<u32>,
+}
+
+impl FigFont {
+    pub fn from_bytes(data: &[u8]) -> Result<FigFont, CacaError> {
+        let text = String::from_utf8_lossy(data);
+        let mut lines = text.lines();
+
+        let header = lines.next().unwrap_or("");
+        if !(header.starts_with("flf2a") || header.starts_with("tlf2a")) {
+            return Err(CacaError::InvalidFontData);
+        }
+        // The signature is followed by the hardblank character.
+        let params: Vec<i32> = header.chars().skip(6).collect::<String>()
+            .split_whitespace()
+            .map(|param| param.parse::<i32>())
+            .take_while(|param| param.is_ok())
+            .map(|param| param.unwrap())
+            .collect();
+        if params.len() < 5 {
+            return Err(CacaError::InvalidFontData);
+        }
+        let (height, baseline, comment_lines) = (params[0], params[1], params[4]);
+        if height < 1 || baseline < 1 || baseline > height || comment_lines < 0 {
+            return Err(CacaError::InvalidFontData);
+        }
+
+        let mut lines = lines.skip(comment_lines as usize);
+        let mut code_points = Vec::new();
+        for code_point in (32..127).chain(REQUIRED_CHARS.iter().cloned()) {
+            if lines.by_ref().take(height as usize).count() < height as usize {
+                break;
+            }
+            code_points.push(code_point);
+        }
+        while let Some(tag_line) = lines.next() {
+            let code_point = tag_line.split_whitespace().next().and_then(parse_code_tag);
+            if lines.by_ref().take(height as usize).count() < height as usize {
+                break;
+            }
+            // Negative codes do not map to Unicode characters.
+            if let Some(code_point_) = code_point {
+                if code_point_ >= 0 && code_point_ <= 0x10ffff {
+                    code_points.push(code_point_ as u32);
+                }
+            }
+        }
+        code_points.sort();
+        code_points.dedup();
+
+        Ok(FigFont {
+            data: data.to_vec(),
+            height: height,
+            baseline: baseline,
+            code_points: code_points,
+        })
+    }
+
+    /// Loads a font file, which may be compressed.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<FigFont, CacaError> {
+        let mut data = Vec::new();
+        CacaFile::open(path)?.read_to_end(&mut data)?;
+        FigFont::from_bytes(&data)
+    }
+
+    /// The height of a character, in lines.
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    /// The number of lines from the top of a character to its baseline.
+    pub fn baseline(&self) -> i32 {
+        self.baseline
+    }
+
+    /// The code points the font defines a character for, in ascending order.
+    pub fn code_points(&self) -> &[u32] {
+        &self.code_points
+    }
+
+    pub fn has_char(&self, c: char) -> bool {
+        self.code_points.binary_search(&(c as u32)).is_ok()
+    }
+}
+
+// Code tags are decimal, octal with a leading 0, or hexadecimal with a leading
+// 0x, and may be negative.
+fn parse_code_tag(tag: &str) -> Option<i64> {
+    let (negative, digits) = if tag.starts_with('-') {
+        (true, &tag[1..])
+    } else {
+        (false, tag)
+    };
+    let value = if digits.starts_with("0x") || digits.starts_with("0X") {
+        i64::from_str_radix(&digits[2..], 16)
+    } else if digits.len() > 1 && digits.starts_with('0') {
+        i64::from_str_radix(&digits[1..], 8)
+    } else {
+        digits.parse::<i64>()
+    };
+    value.ok().map(|value_| if negative { -value_ } else { value_ })
+}
+
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum FigFontSmush {
+    Default,
+    Kern,
+    Smush,
+    Overlap,
+    None,
+}
+
+impl FigFontSmush {
+    fn to_cstring(&self) -> CString {
+        let smush_name = match *self {
+            FigFontSmush::Default => "default",
+            FigFontSmush::Kern    => "kern",
+            FigFontSmush::Smush   => "smush",
+            FigFontSmush::Overlap => "overlap",
+            FigFontSmush::None    => "none",
+        };
+        CString::new(smush_name).unwrap()
+    }
+}
+
+impl CanvasView {
+    /// Selects the FIGlet font used for FIGlet rendering, either by a path or
+    /// by the name of a font in the system FIGlet font directory. Once a font
+    /// is set, the canvas is resized to fit the text rendered with it.
+    pub fn set_figfont(&mut self, name: &str) -> CacaResult {
+        let name_cstring = CString::new(name).unwrap();
+        let result = unsafe { caca_canvas_set_figfont(self.raw(), name_cstring.as_ptr()) };
+        if result == 0 {
+            Ok(())
+        } else {
+            let errno = errno().0;
+            match errno {
+                libc::ENOENT => Err(CacaError::FontNotFound),
+                libc::EINVAL => Err(CacaError::InvalidFontData),
+                libc::ENOMEM => Err(CacaError::NotEnoughMemory),
+                _            => Err(CacaError::Unknown(errno)),
+            }
+        }
+    }
+
+    /// Selects a FIGlet font held in memory. libcaca can only load FIGlet
+    /// fonts from files, so the font is written to a file in a private
+    /// temporary directory while it is being loaded.
+    pub fn set_figfont_from(&mut self, font: &FigFont) -> CacaResult {
+        let dir = create_figfont_dir()?;
+        let path = dir.join("font.flf");
+        let result = OpenOptions::new().write(true).create_new(true).open(&path)
+            .and_then(|mut file| file.write_all(&font.data))
+            .map_err(CacaError::from)
+            .and_then(|_| self.set_figfont(&path.to_string_lossy()));
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_dir(&dir);
+        result
+    }
+
+    pub fn set_figfont_smush(&mut self, smush: &FigFontSmush) -> CacaResult {
+        let smush_cstring = smush.to_cstring();
+        figlet_result(unsafe { caca_set_figfont_smush(self.raw(), smush_cstring.as_ptr()) })
+    }
+
+    /// Sets the width in characters after which FIGlet text wraps.
+    pub fn set_figfont_width(&mut self, width: i32) -> CacaResult {
+        figlet_result(unsafe { caca_set_figfont_width(self.raw(), width) })
+    }
+
+    pub fn put_figchar(&mut self, c: char) -> CacaResult {
+        figlet_result(unsafe { caca_put_figchar(self.raw(), c as u32) })
+    }
+
+    /// Finishes the FIGlet text rendered so far, trimming the canvas to its
+    /// size. The next character starts a new block of text.
+    pub fn flush_figlet(&mut self) -> CacaResult {
+        figlet_result(unsafe { caca_flush_figlet(self.raw()) })
+    }
+
+    pub fn put_figlet_str(&mut self, s: &str) -> CacaResult {
+        for c in s.chars() {
+            self.put_figchar(c)?;
+        }
+        self.flush_figlet()
+    }
+}
+
+// Creates a new directory only the current user can access. Creating it fails
+// if anything already exists at that path, symbolic links included, so the
+// next name is tried instead.
+fn create_figfont_dir() -> io::Result<PathBuf> {
+    let mut builder = DirBuilder::new();
+    #[cfg(unix)] builder.mode(0o700);
+    for _ in 0..FIGFONT_DIR_ATTEMPTS {
+        let dir = env::temp_dir().join(format!("caca-rs-{}-{}", process::id(),
+                                               NEXT_FIGFONT_DIR.fetch_add(1, Ordering::SeqCst)));
+        match builder.create(&dir) {
+            Ok(()) => return Ok(dir),
+            Err(ref err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(err) => return Err(err),
+        }
+    }
+    Err(io::Error::new(io::ErrorKind::AlreadyExists,
+                       "could not create a temporary directory for the font"))
+}
+
+// The FIGlet functions only fail when no font was set.
+fn figlet_result(result: c_int) -> CacaResult {
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(CacaError::FigfontNotSet)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use Canvas;
+    use super::*;
+
+    fn test_font() -> Vec<u8> {
+        let mut font = String::from("flf2a$ 1 1 3 -1 1\nTest font\n");
+        for _ in (32..127).chain(REQUIRED_CHARS.iter().cloned()) {
+            font.push_str("#@@\n");
+        }
+        font.push_str("0x263A SMILEY\no@@\n");
+        font.push_str("-2 NOT UNICODE\nx@@\n");
+        font.into_bytes()
+    }
+
+    #[test]
+    fn test_figfont_from_bytes() {
+        let font = FigFont::from_bytes(&test_font());
+        assert!(font.is_ok(), "{:?}", font.err());
+        let font = font.unwrap();
+        assert_eq!(font.height(), 1);
+        assert_eq!(font.baseline(), 1);
+        assert_eq!(font.code_points().len(), 95 + 7 + 1);
+        assert!(font.has_char('A'));
+        assert!(font.has_char('\u{263A}'));
+        assert!(!font.has_char('\u{263B}'));
+
+        assert!(FigFont::from_bytes(b"not a font").is_err());
+        assert!(FigFont::from_bytes(b"flf2a$ 0 0 3 -1 0\n").is_err());
+
+        assert_eq!(parse_code_tag("0x263A"), Some(0x263a));
+        assert_eq!(parse_code_tag("0177"), Some(0o177));
+        assert_eq!(parse_code_tag("-12"), Some(-12));
+        assert_eq!(parse_code_tag("nope"), None);
+    }
+
+    #[test]
+    fn test_set_figfont_from() {
+        let font = FigFont::from_bytes(&test_font()).unwrap();
+        let mut canvas = Canvas::new(0, 0).unwrap();
+        let result = canvas.set_figfont_from(&font);
+        assert!(result.is_ok(), "{:?}", result.err());
+
+        let result = canvas.put_figlet_str("caca");
+        assert!(result.is_ok(), "{:?}", result.err());
+        assert_eq!(canvas.height(), 1);
+        assert!(canvas.width() >= 4);
+    }
+
+    #[test]
+    fn test_figfont_dir() {
+        // A name taken by someone else is skipped rather than reused.
+        let taken = env::temp_dir().join(format!("caca-rs-{}-{}", process::id(),
+                                                 NEXT_FIGFONT_DIR.load(Ordering::SeqCst)));
+        let _ = fs::create_dir(&taken);
+
+        let dir = create_figfont_dir();
+        assert!(dir.is_ok(), "{:?}", dir.err());
+        let dir = dir.unwrap();
+        assert!(dir != taken);
+        #[cfg(unix)] {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(fs::metadata(&dir).unwrap().permissions().mode() & 0o777, 0o700);
+        }
+
+        fs::remove_dir(&dir).unwrap();
+        let _ = fs::remove_dir(&taken);
+    }
+
+    #[test]
+    fn test_no_figfont() {
+        let mut canvas = Canvas::new(10, 10).unwrap();
+        assert!(canvas.set_figfont("/nonexistent/caca-rs.flf").is_err());
+        assert!(canvas.put_figlet_str("caca").is_err());
+        assert!(canvas.set_figfont_smush(&FigFontSmush::Kern).is_err());
+        assert!(canvas.set_figfont_width(40).is_err());
+    }
+}
@@ -0,0 +1,209 @@
+//! Transparent access to plain and zlib-compressed files, through libcaca's
+//! own file functions.
+
+use std::cmp;
+use std::ffi::CString;
+use std::fs::File;
+use std::io::{self, Read, Write, BufRead};
+use std::path::Path;
+use std::ptr;
+use libc::{c_char, c_int, c_void, size_t};
+
+const BUFFER_SIZE: usize = 8 * 1024;
+
+#[repr(C)]
+struct CacaFileRaw {
+    _private: [u8; 0],
+}
+
+extern "C" {
+    fn caca_file_open(path: *const c_char, mode: *const c_char) -> *mut CacaFileRaw;
+    fn caca_file_close(fp: *mut CacaFileRaw) -> c_int;
+    fn caca_file_tell(fp: *mut CacaFileRaw) -> u64;
+    fn caca_file_read(fp: *mut CacaFileRaw, ptr: *mut c_void, size: size_t) -> size_t;
+    fn caca_file_write(fp: *mut CacaFileRaw, ptr: *const c_void, size: size_t) -> size_t;
+    fn caca_file_eof(fp: *mut CacaFileRaw) -> c_int;
+}
+
+/// A file opened through libcaca. Compressed files are decompressed
+/// transparently when reading, and files created for writing are compressed
+/// if libcaca was built with zlib support. Compressed data is only completely
+/// written once the file is closed, so `close` should be used to check that
+/// writing succeeded.
+pub struct CacaFile {
+    file: *mut CacaFileRaw,
+    buf: Vec<u8>,
+    pos: usize,
+    cap: usize,
+}
+
+impl CacaFile {
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<CacaFile> {
+        // libcaca does not leave a meaningful errno behind when opening
+        // fails, so the file is opened with std first to get a proper error.
+        File::open(path.as_ref())?;
+        CacaFile::open_with_mode(path.as_ref(), "rb")
+    }
+
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<CacaFile> {
+        File::create(path.as_ref())?;
+        CacaFile::open_with_mode(path.as_ref(), "wb")
+    }
+
+    fn open_with_mode(path: &Path, mode: &str) -> io::Result<CacaFile> {
+        let path_str = match path.to_str() {
+            Some(path_str_) => path_str_,
+            None            => return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                                         "path is not valid UTF-8")),
+        };
+        let path_cstring = CString::new(path_str)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+        let mode_cstring = CString::new(mode).unwrap();
+
+        let file = unsafe { caca_file_open(path_cstring.as_ptr(), mode_cstring.as_ptr()) };
+        if file.is_null() {
+            Err(io::Error::new(io::ErrorKind::Other, "libcaca failed to open the file"))
+        } else {
+            Ok(CacaFile {
+                file: file,
+                buf: vec![0; BUFFER_SIZE],
+                pos: 0,
+                cap: 0,
+            })
+        }
+    }
+
+    /// The position in the uncompressed stream, not counting data that has
+    /// been buffered but not read yet.
+    pub fn tell(&self) -> u64 {
+        let position = unsafe { caca_file_tell(self.file) };
+        position - (self.cap - self.pos) as u64
+    }
+
+    /// Closes the file, reporting errors that dropping it would ignore, such
+    /// as failing to write the end of a compressed stream.
+    pub fn close(mut self) -> io::Result<()> {
+        let file = self.file;
+        self.file = ptr::null_mut();
+        if unsafe { caca_file_close(file) } == 0 {
+            Ok(())
+        } else {
+            Err(io::Error::new(io::ErrorKind::Other, "failed to close file"))
+        }
+    }
+
+    pub fn eof(&self) -> bool {
+        self.pos == self.cap && unsafe { caca_file_eof(self.file) != 0 }
+    }
+
+    fn read_raw(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        read_raw(self.file, buf)
+    }
+}
+
+fn read_raw(file: *mut CacaFileRaw, buf: &mut [u8]) -> io::Result<usize> {
+    let read = unsafe { caca_file_read(file, buf.as_mut_ptr() as *mut c_void, buf.len() as size_t) };
+    // zlib reports errors as -1, which comes back as a huge size_t.
+    if read as usize > buf.len() {
+        Err(io::Error::new(io::ErrorKind::Other, "failed to read from file"))
+    } else {
+        Ok(read as usize)
+    }
+}
+
+impl Read for CacaFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        // Large reads skip the internal buffer entirely if it is empty.
+        if self.pos == self.cap && buf.len() >= self.buf.len() {
+            return self.read_raw(buf);
+        }
+        let read = {
+            let mut remaining = self.fill_buf()?;
+            remaining.read(buf)?
+        };
+        self.consume(read);
+        Ok(read)
+    }
+}
+
+impl BufRead for CacaFile {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.pos == self.cap {
+            self.cap = read_raw(self.file, &mut self.buf)?;
+            self.pos = 0;
+        }
+        Ok(&self.buf[self.pos..self.cap])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos = cmp::min(self.pos + amt, self.cap);
+    }
+}
+
+impl Write for CacaFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = unsafe { caca_file_write(self.file, buf.as_ptr() as *const c_void, buf.len() as size_t) };
+        Ok(written as usize)
+    }
+
+    // libcaca cannot flush files, data is written when closing them.
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for CacaFile {
+    fn drop(&mut self) {
+        if !self.file.is_null() {
+            unsafe {
+                caca_file_close(self.file);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+    use super::*;
+
+    #[test]
+    fn test_write_and_read() {
+        let path = env::temp_dir().join("caca-rs-test-file.txt.gz");
+        {
+            let file = CacaFile::create(&path);
+            assert!(file.is_ok(), "{:?}", file.err());
+            let mut file = file.unwrap();
+            file.write_all(b"first line\nsecond line\n").unwrap();
+            let result = file.close();
+            assert!(result.is_ok(), "{:?}", result.err());
+        }
+
+        let file = CacaFile::open(&path);
+        assert!(file.is_ok(), "{:?}", file.err());
+        let mut file = file.unwrap();
+        let mut line = String::new();
+        file.read_line(&mut line).unwrap();
+        assert_eq!(line, "first line\n");
+        assert_eq!(file.tell(), 11);
+
+        let mut rest = String::new();
+        file.read_to_string(&mut rest).unwrap();
+        assert_eq!(rest, "second line\n");
+        assert!(file.eof());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_open_missing() {
+        let path = env::temp_dir().join("caca-rs-test-missing.txt");
+        let file = CacaFile::open(&path);
+        assert_eq!(file.err().map(|err| err.kind()), Some(io::ErrorKind::NotFound));
+
+        let path = env::temp_dir().join("caca-rs-test-missing").join("file.txt.gz");
+        let file = CacaFile::create(&path);
+        assert_eq!(file.err().map(|err| err.kind()), Some(io::ErrorKind::NotFound));
+    }
+}
@@ -0,0 +1,230 @@
+use std::ffi::{CStr, CString};
+#[cfg(feature = "png")] use std::fs::File;
+#[cfg(feature = "png")] use std::io::{self, BufWriter};
+use std::marker::PhantomData;
+#[cfg(feature = "png")] use std::path::Path;
+use errno::errno;
+use libc::{self, c_void, size_t};
+
+use caca::*;
+use ::{CanvasView, CacaError, CacaResult};
+
+/// A bitmap font, used to render canvases to pixel buffers. Fonts loaded from
+/// memory borrow their data, since libcaca does not copy it.
+pub struct Font<'a> {
+    font: *mut CacaFontRaw,
+    _phantom: PhantomData<&'a [u8]>,
+}
+
+impl Font<'static> {
+    /// Loads one of the fonts built into libcaca, by a name from
+    /// `Font::font_list()`.
+    pub fn builtin(name: &str) -> Result<Font<'static>, CacaError> {
+        let name_cstring = CString::new(name).unwrap();
+        let font = unsafe { caca_load_font(name_cstring.as_ptr() as *const c_void, 0) };
+        Font::from_raw(font)
+    }
+
+    pub fn font_list() -> Vec<String> {
+        let mut names = Vec::new();
+        unsafe {
+            let mut entry = caca_get_font_list();
+            while !(*entry).is_null() {
+                names.push(CStr::from_ptr(*entry).to_string_lossy().into_owned());
+                entry = entry.offset(1);
+            }
+        }
+        names
+    }
+}
+
+impl<'a> Font<'a> {
+    pub fn from_bytes(data: &'a [u8]) -> Result<Font<'a>, CacaError> {
+        // A zero length would make libcaca treat the data as a font name.
+        if data.is_empty() {
+            return Err(CacaError::InvalidFontData);
+        }
+        let font = unsafe { caca_load_font(data.as_ptr() as *const c_void, data.len() as size_t) };
+        Font::from_raw(font)
+    }
+
+    fn from_raw(font: *mut CacaFontRaw) -> Result<Font<'a>, CacaError> {
+        if font.is_null() {
+            let errno = errno().0;
+            match errno {
+                libc::ENOENT => Err(CacaError::FontNotFound),
+                libc::EINVAL => Err(CacaError::InvalidFontData),
+                libc::ENOMEM => Err(CacaError::NotEnoughMemory),
+                _            => Err(CacaError::Unknown(errno)),
+            }
+        } else {
+            Ok(Font {
+                font: font,
+                _phantom: PhantomData,
+            })
+        }
+    }
+
+    /// The width of a glyph, in pixels.
+    pub fn width(&self) -> i32 {
+        unsafe { caca_get_font_width(self.font) }
+    }
+
+    /// The height of a glyph, in pixels.
+    pub fn height(&self) -> i32 {
+        unsafe { caca_get_font_height(self.font) }
+    }
+
+    /// The Unicode blocks supported by the font, as `[start, stop)` ranges.
+    pub fn blocks(&self) -> Vec<(u32, u32)> {
+        let mut blocks = Vec::new();
+        unsafe {
+            let mut block = caca_get_font_blocks(self.font);
+            while *block.offset(1) != 0 {
+                blocks.push((*block, *block.offset(1)));
+                block = block.offset(2);
+            }
+        }
+        blocks
+    }
+
+    pub unsafe fn as_ptr(&self) -> *const CacaFontRaw {
+        self.font
+    }
+}
+
+impl<'a> Drop for Font<'a> {
+    fn drop(&mut self) {
+        unsafe {
+            caca_free_font(self.font);
+        }
+    }
+}
+
+impl CanvasView {
+    /// Renders the canvas with the given font to an image of `width` by
+    /// `height` pixels, stored as one `0xAARRGGBB` value per pixel. The canvas
+    /// fills `width() * font.width()` by `height() * font.height()` pixels.
+    pub fn render(&self, font: &Font, width: i32, height: i32) -> Result<Vec<u32>, CacaError> {
+        if width < 0 || height < 0 {
+            return Err(CacaError::InvalidSize);
+        }
+        let mut buf = vec![0; width as usize * height as usize * 4];
+        self.render_into(font, &mut buf, width, height, width * 4)?;
+        Ok(buf.chunks(4)
+           .map(|p| (p[0] as u32) << 24 | (p[1] as u32) << 16 | (p[2] as u32) << 8 | p[3] as u32)
+           .collect())
+    }
+
+    /// Renders the canvas into a caller supplied buffer, with 4 bytes per
+    /// pixel in A, R, G, B order and `pitch` bytes per line.
+    pub fn render_into(&self, font: &Font, buf: &mut [u8],
+                       width: i32, height: i32, pitch: i32) -> CacaResult {
+        if width < 0 || height < 0 || (pitch as i64) < width as i64 * 4 ||
+            (buf.len() as u64) < pitch as u64 * height as u64 {
+            return Err(CacaError::InvalidSize);
+        }
+        let result = unsafe { caca_render_canvas(self.raw(), font.as_ptr(),
+                                                 buf.as_mut_ptr() as *mut c_void,
+                                                 width, height, pitch) };
+        if result == 0 {
+            Ok(())
+        } else {
+            let errno = errno().0;
+            match errno {
+                libc::EINVAL => Err(CacaError::InvalidSize),
+                _            => Err(CacaError::Unknown(errno)),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "png")]
+impl CanvasView {
+    /// Saves the canvas rendered with the given font as a PNG image, one
+    /// glyph per character cell.
+    pub fn save_png<P: AsRef<Path>>(&self, path: P, font: &Font) -> CacaResult {
+        let (width, height) = (self.width() * font.width(), self.height() * font.height());
+        let mut buf = vec![0; width as usize * height as usize * 4];
+        self.render_into(font, &mut buf, width, height, width * 4)?;
+
+        // libcaca renders ARGB, PNG expects RGBA.
+        for pixel in buf.chunks_mut(4) {
+            pixel.rotate_left(1);
+        }
+
+        let file = File::create(path)?;
+        let mut encoder = png::Encoder::new(BufWriter::new(file), width as u32, height as u32);
+        encoder.set_color(png::ColorType::RGBA);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(io::Error::from)?;
+        writer.write_image_data(&buf).map_err(io::Error::from)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use Canvas;
+    use super::*;
+
+    #[test]
+    fn test_builtin_font() {
+        let names = Font::font_list();
+        assert!(!names.is_empty());
+
+        let font = Font::builtin(&names[0]);
+        assert!(font.is_ok(), "{:?}", font.err());
+        let font = font.unwrap();
+        assert!(font.width() > 0);
+        assert!(font.height() > 0);
+        assert!(!font.blocks().is_empty());
+
+        assert!(Font::builtin("No Such Font 12").is_err());
+    }
+
+    #[test]
+    fn test_render() {
+        let font = Font::builtin(&Font::font_list()[0]).unwrap();
+        let mut canvas = Canvas::new(2, 1).unwrap();
+        canvas.set_color_ansi(&::AnsiColor::White, &::AnsiColor::Black);
+        canvas.put_str(0, 0, "#");
+
+        let (width, height) = (2 * font.width(), font.height());
+        let pixels = canvas.render(&font, width, height);
+        assert!(pixels.is_ok(), "{:?}", pixels.err());
+        let pixels = pixels.unwrap();
+        assert_eq!(pixels.len(), (width * height) as usize);
+        assert!(pixels.iter().any(|&p| p != pixels[0]));
+
+        let mut buf = vec![0; 4];
+        assert!(canvas.render_into(&font, &mut buf, width, height, width * 4).is_err());
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    fn test_save_png() {
+        use std::env;
+        use std::fs;
+        use std::io::Read;
+
+        let font = Font::builtin(&Font::font_list()[0]).unwrap();
+        let mut canvas = Canvas::new(4, 2).unwrap();
+        canvas.put_str(0, 0, "png");
+
+        let path = env::temp_dir().join("caca-rs-test-font.png");
+        let result = canvas.save_png(&path, &font);
+        assert!(result.is_ok(), "{:?}", result.err());
+
+        let mut header = [0; 8];
+        fs::File::open(&path).unwrap().read_exact(&mut header).unwrap();
+        assert_eq!(&header, b"\x89PNG\r\n\x1a\n");
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_font_data() {
+        assert!(Font::from_bytes(b"").is_err());
+        assert!(Font::from_bytes(b"not a font").is_err());
+    }
+}
@@ -0,0 +1,94 @@
+use errno::errno;
+use libc::{self, c_int};
+
+use caca::*;
+use ::{CanvasView, CacaError, CacaResult};
+
+impl CanvasView {
+    /// Inverts the colors of the canvas, leaving the characters untouched.
+    pub fn invert(&mut self) {
+        unsafe { caca_invert(self.raw()) };
+    }
+
+    /// Mirrors the canvas horizontally, replacing characters with their
+    /// mirrored counterparts where possible (`/` becomes `\`, `(` becomes `)`).
+    pub fn flip(&mut self) {
+        unsafe { caca_flip(self.raw()) };
+    }
+
+    /// Mirrors the canvas vertically, replacing characters with their
+    /// mirrored counterparts where possible.
+    pub fn flop(&mut self) {
+        unsafe { caca_flop(self.raw()) };
+    }
+
+    pub fn rotate_180(&mut self) {
+        unsafe { caca_rotate_180(self.raw()) };
+    }
+
+    /// Rotates the canvas 90 degrees counterclockwise. Since characters are
+    /// about twice as high as they are wide, the canvas becomes twice the
+    /// height wide and half the width high.
+    pub fn rotate_left(&mut self) -> CacaResult {
+        resize_result(unsafe { caca_rotate_left(self.raw()) })
+    }
+
+    /// Rotates the canvas 90 degrees clockwise, with the same size change as
+    /// `rotate_left`.
+    pub fn rotate_right(&mut self) -> CacaResult {
+        resize_result(unsafe { caca_rotate_right(self.raw()) })
+    }
+
+    /// Rotates the canvas 90 degrees counterclockwise, stretching it so that
+    /// its size is only swapped.
+    pub fn stretch_left(&mut self) -> CacaResult {
+        resize_result(unsafe { caca_stretch_left(self.raw()) })
+    }
+
+    /// Rotates the canvas 90 degrees clockwise, stretching it so that its
+    /// size is only swapped.
+    pub fn stretch_right(&mut self) -> CacaResult {
+        resize_result(unsafe { caca_stretch_right(self.raw()) })
+    }
+}
+
+fn resize_result(result: c_int) -> CacaResult {
+    if result == 0 {
+        Ok(())
+    } else {
+        let errno = errno().0;
+        match errno {
+            libc::EBUSY  => Err(CacaError::CanvasInUse),
+            libc::ENOMEM => Err(CacaError::NotEnoughMemory),
+            _            => Err(CacaError::Unknown(errno)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {Canvas, ExportFormat};
+
+    #[test]
+    fn test_flip() {
+        let mut canvas = Canvas::new(2, 1).unwrap();
+        canvas.put_str(0, 0, "/(");
+        canvas.flip();
+        let text = String::from_utf8(canvas.export(ExportFormat::Utf8).unwrap()).unwrap();
+        assert!(text.contains(")\\"));
+    }
+
+    #[test]
+    fn test_stretch() {
+        let mut canvas = Canvas::new(6, 3).unwrap();
+        let result = canvas.stretch_left();
+        assert!(result.is_ok(), "{:?}", result.err());
+        assert_eq!(canvas.width(), 3);
+        assert_eq!(canvas.height(), 6);
+
+        let result = canvas.stretch_right();
+        assert!(result.is_ok(), "{:?}", result.err());
+        assert_eq!(canvas.width(), 6);
+        assert_eq!(canvas.height(), 3);
+    }
+}